@@ -0,0 +1,117 @@
+//! Compares routing a button press through one collector per session with routing it through an [InteractionDispatcher].
+//!
+//! Serenity runs the filters of every collector for every interaction, which is simulated here with the same filters the
+//! paginator sessions use. Run with `cargo bench --bench dispatch`.
+
+use {
+    poise::serenity_prelude::{ChannelId, ComponentInteraction, FullEvent, Interaction, UserId},
+    poise_paginator::InteractionDispatcher,
+    std::{
+        hint::black_box,
+        time::{Duration, Instant},
+    },
+};
+
+const ITERATIONS: u32 = 10_000;
+
+type Filter = Box<dyn Fn(&ComponentInteraction) -> Option<ComponentInteraction>>;
+
+fn main() {
+    println!(
+        "{:>8} {:>14} {:>14}",
+        "sessions", "collectors", "dispatcher"
+    );
+
+    for sessions in [10, 100, 1_000, 10_000] {
+        // The press targets the most recent session, the others belong to other users in other channels.
+        let press = press(sessions, sessions, sessions);
+
+        let filters = (1..=sessions).map(collector_filter).collect::<Vec<_>>();
+
+        let collectors = measure(|| filters.iter().filter_map(|filter| filter(&press)).count());
+
+        let dispatcher = InteractionDispatcher::new();
+
+        let _subscriptions = (1..=sessions)
+            .map(|id| dispatcher.subscribe(id, UserId::new(id), ChannelId::new(id)))
+            .collect::<Vec<_>>();
+
+        let event = FullEvent::InteractionCreate {
+            interaction: Interaction::Component(press),
+        };
+
+        let dispatched = measure(|| dispatcher.handle_event(&event));
+
+        println!("{sessions:>8} {collectors:>14?} {dispatched:>14?}");
+    }
+}
+
+/// The filters of the collector of a session, like serenity runs them for every interaction.
+fn collector_filter(id: u64) -> Filter {
+    let prefix = id.to_string();
+
+    Box::new(move |press| {
+        let matches = press.user.id == UserId::new(id)
+            && press.channel_id == ChannelId::new(id)
+            && press.data.custom_id.starts_with(&prefix);
+
+        // Collectors clone the interactions they match.
+        matches.then(|| press.clone())
+    })
+}
+
+/// The average duration of routing a single press.
+fn measure<T>(mut route: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        black_box(route());
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn press(session_id: u64, user_id: u64, channel_id: u64) -> ComponentInteraction {
+    serde_json::from_value(serde_json::json!({
+        "id": "1",
+        "application_id": "1",
+        "type": 3,
+        "token": "token",
+        "version": 1,
+        "channel_id": channel_id.to_string(),
+        "locale": "en-US",
+        "entitlements": [],
+        "user": {
+            "id": user_id.to_string(),
+            "username": "user",
+            "discriminator": "0",
+            "avatar": null,
+        },
+        "data": {
+            "custom_id": format!("{session_id}_forward"),
+            "component_type": 2,
+        },
+        "message": {
+            "id": "1",
+            "channel_id": channel_id.to_string(),
+            "author": {
+                "id": "1",
+                "username": "bot",
+                "discriminator": "0",
+                "avatar": null,
+            },
+            "content": "",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        },
+    }))
+    .expect("valid interaction")
+}
@@ -0,0 +1,286 @@
+//! A stand-in for the Discord HTTP API, recording the exact requests serenity sends to it.
+//!
+//! Serenity's `Http` is pointed at it through its proxy setting, so the requests go through the real serialization path.
+//! Shared by the end-to-end tests of the crate and of the examples as a dev-dependency.
+
+use {
+    hyper::{
+        Body, Response, Server,
+        service::{make_service_fn, service_fn},
+    },
+    poise::serenity_prelude::{
+        ApplicationId, CommandInteraction, ComponentInteraction, FullEvent, Http, HttpBuilder,
+        Interaction,
+    },
+    poise_paginator::InteractionDispatcher,
+    serde_json::{Value, json},
+    std::{
+        collections::VecDeque,
+        convert::Infallible,
+        net::SocketAddr,
+        sync::Arc,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::{
+        Mutex,
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+    },
+};
+
+/// The id of the application, which the paths of followups and response edits contain.
+pub const APPLICATION_ID: u64 = 1;
+
+/// The id of the message sent in response to the command.
+pub const MESSAGE_ID: u64 = 10;
+
+/// The user running the command and pressing the buttons.
+pub const OWNER_ID: u64 = 20;
+
+/// The channel the command is run in.
+pub const CHANNEL_ID: u64 = 30;
+
+/// A request received by the stand-in server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Value,
+}
+
+/// A failure the stand-in server responds with instead of succeeding, as its status and Discord JSON error code.
+#[derive(Debug, Clone, Copy)]
+struct Failure {
+    status: u16,
+    code: u64,
+}
+
+type Failures = Arc<std::sync::Mutex<VecDeque<Failure>>>;
+
+pub struct Discord {
+    pub http: Arc<Http>,
+    pub dispatcher: InteractionDispatcher,
+    requests: Mutex<UnboundedReceiver<Request>>,
+    failures: Failures,
+    next_command_id: std::sync::atomic::AtomicU64,
+    next_interaction_id: std::sync::atomic::AtomicU64,
+}
+
+impl Discord {
+    /// Starts the server on a free local port.
+    pub async fn start() -> Self {
+        let (sender, requests) = mpsc::unbounded_channel();
+        let failures = Failures::default();
+
+        let make_service = make_service_fn({
+            let failures = Arc::clone(&failures);
+
+            move |_| {
+                let sender = sender.clone();
+                let failures = Arc::clone(&failures);
+
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request| {
+                        handle(sender.clone(), Arc::clone(&failures), request)
+                    }))
+                }
+            }
+        });
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let address = server.local_addr();
+        tokio::spawn(server);
+
+        let http = HttpBuilder::new("token")
+            .proxy(format!("http://{address}"))
+            .ratelimiter_disabled(true)
+            .application_id(ApplicationId::new(APPLICATION_ID))
+            .build();
+
+        Self {
+            http: Arc::new(http),
+            dispatcher: InteractionDispatcher::new(),
+            requests: Mutex::new(requests),
+            failures,
+            next_command_id: std::sync::atomic::AtomicU64::new(0),
+            next_interaction_id: std::sync::atomic::AtomicU64::new(1),
+        }
+    }
+
+    /// Waits for the next request, in the order they were received.
+    pub async fn next_request(&self) -> Request {
+        self.requests
+            .lock()
+            .await
+            .recv()
+            .await
+            .expect("The server is running")
+    }
+
+    /// Fails the next request that isn't failed already with the given status and Discord JSON error code.
+    ///
+    /// https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+    pub fn fail_next(&self, status: u16, code: u64) {
+        self.failures
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push_back(Failure { status, code });
+    }
+
+    /// A slash command interaction that was just created, so its token is valid.
+    pub fn command(&self) -> CommandInteraction {
+        self.command_created(Duration::ZERO)
+    }
+
+    /// A slash command interaction created the given time ago, whose token expires 15 minutes after that.
+    pub fn command_created(&self, ago: Duration) -> CommandInteraction {
+        // The increment of the snowflake tells apart commands created in the same millisecond.
+        let id = snowflake(ago)
+            + self
+                .next_command_id
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "application_id": APPLICATION_ID.to_string(),
+            "type": 2,
+            "token": "command-token",
+            "version": 1,
+            "channel_id": CHANNEL_ID.to_string(),
+            "locale": "en-US",
+            "entitlements": [],
+            "attachment_size_limit": 10 * 1024 * 1024,
+            "user": user(OWNER_ID),
+            "data": {
+                "id": "1",
+                "name": "paginate",
+                "type": 1,
+            },
+        }))
+        .expect("The command interaction is valid")
+    }
+
+    /// The event of the given user pressing the button with the given id on the message, as the gateway delivers it.
+    pub fn press_event(&self, custom_id: &str, user_id: u64) -> FullEvent {
+        let id = self
+            .next_interaction_id
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        let press: ComponentInteraction = serde_json::from_value(json!({
+            "id": id.to_string(),
+            "application_id": APPLICATION_ID.to_string(),
+            "type": 3,
+            "token": format!("press-token-{id}"),
+            "version": 1,
+            "channel_id": CHANNEL_ID.to_string(),
+            "locale": "en-US",
+            "entitlements": [],
+            "attachment_size_limit": 10 * 1024 * 1024,
+            "user": user(user_id),
+            "data": {
+                "custom_id": custom_id,
+                "component_type": 2,
+            },
+            "message": message(),
+        }))
+        .expect("The component interaction is valid");
+
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(press),
+        }
+    }
+
+    /// Presses the button with the given id on the message, routing the press through the dispatcher like the gateway would.
+    pub async fn press(&self, custom_id: &str) {
+        let event = self.press_event(custom_id, OWNER_ID);
+
+        // The session subscribes to the dispatcher once the first page was sent.
+        for _ in 0..100 {
+            if self.dispatcher.handle_event(&event) {
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        panic!("The press on {custom_id} wasn't routed to a session");
+    }
+}
+
+async fn handle(
+    sender: UnboundedSender<Request>,
+    failures: Failures,
+    request: hyper::Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let method = request.method().to_string();
+    let path = request.uri().path().to_owned();
+
+    let body = hyper::body::to_bytes(request.into_body())
+        .await
+        .unwrap_or_default();
+    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    let callback = path.ends_with("/callback");
+
+    sender.send(Request { method, path, body }).ok();
+
+    let failure = failures
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .pop_front();
+
+    // Interaction callbacks have no content, everything else returns the message.
+    let response = match (failure, callback) {
+        (Some(Failure { status, code }), _) => Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({ "code": code, "message": "Scripted failure" }).to_string(),
+            )),
+        (None, true) => Response::builder().status(204).body(Body::empty()),
+        (None, false) => Response::builder()
+            .header("content-type", "application/json")
+            .body(Body::from(message().to_string())),
+    };
+
+    Ok(response.expect("The response is valid"))
+}
+
+fn user(id: u64) -> Value {
+    json!({
+        "id": id.to_string(),
+        "username": "user",
+        "discriminator": "0",
+        "avatar": null,
+    })
+}
+
+fn message() -> Value {
+    json!({
+        "id": MESSAGE_ID.to_string(),
+        "channel_id": CHANNEL_ID.to_string(),
+        "author": user(APPLICATION_ID),
+        "content": "",
+        "timestamp": "2025-01-01T00:00:00Z",
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+    })
+}
+
+/// A snowflake created the given time ago.
+fn snowflake(ago: Duration) -> u64 {
+    const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+    let created = (SystemTime::now() - ago)
+        .duration_since(UNIX_EPOCH)
+        .expect("The clock is after 1970")
+        .as_millis() as u64;
+
+    (created - DISCORD_EPOCH) << 22
+}
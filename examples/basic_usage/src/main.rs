@@ -3,10 +3,10 @@ use {
         command,
         serenity_prelude::{CreateEmbed, CreateEmbedFooter},
     },
-    std::{sync::Arc, time::Duration},
+    std::time::Duration,
 };
 
-use poise_paginator::{CancellationType, paginate};
+use poise_paginator::{CancellationType, Session, paginate};
 
 // Utilities/types for the example
 use poise_paginator_example::{ApplyIf, Context, Error};
@@ -15,12 +15,14 @@ async fn page_generator(
     _ctx: Context<'_>,
     idx: usize,
     cancellation_type: CancellationType,
-    state: Arc<[&str]>,
+    session: Session<Vec<&'static str>>,
 ) -> Result<CreateEmbed, Error> {
+    let pages = session.state().await;
+
     let embed = CreateEmbed::default()
         .title("Paginator Example")
         // this is safe! Index cannot go out of bounds
-        .description(state[idx])
+        .description(pages[idx])
         .color(poise::serenity_prelude::Color::BLUE)
         .apply_if(cancellation_type == CancellationType::Timeout, |embed| {
             embed.footer(CreateEmbedFooter::new("Cancelled due to timeout"))
@@ -34,14 +36,13 @@ async fn page_generator(
 
 #[command(slash_command)]
 pub async fn test_paginate(ctx: Context<'_>) -> Result<(), Error> {
-    let pages: Arc<[&str]> = [
+    let pages = vec![
         "Page 1: Welcome to the paginator example!",
         "Page 2: This is the second page.",
         "Page 3: Here is the third page.",
         "Page 4: And this is the fourth page.",
         "Page 5: Finally, we have reached the last page.",
-    ]
-    .into();
+    ];
 
     paginate(
         ctx,
@@ -3,12 +3,12 @@ use {
         command,
         serenity_prelude::{CreateEmbed, CreateEmbedFooter},
     },
-    poise_paginator::{PaginationInfo, custom_paginate},
-    poise_paginator_example_custom::Data,
-    std::{sync::Arc, time::Duration},
+    poise_paginator::{CustomAction, PaginationInfo, custom_paginate},
+    poise_paginator_example_custom::{Data, view::SimpleEvent},
+    std::time::Duration,
 };
 
-use poise_paginator::CancellationType;
+use poise_paginator::{CancellationType, Session};
 
 // Utilities/types for the example
 use poise_paginator_example_custom::{ApplyIf, Context, Error};
@@ -18,18 +18,21 @@ impl PaginationInfo for PaginationInformation {
     type View = poise_paginator_example_custom::view::SimpleView;
     type PoiseData = Data;
     type PoiseError = Error;
+    type CustomEvent = SimpleEvent;
 }
 
 async fn page_generator(
     _ctx: Context<'_>,
     idx: usize,
     cancellation_type: CancellationType,
-    state: Arc<[&str]>,
+    session: Session<Vec<&'static str>>,
 ) -> Result<CreateEmbed, Error> {
+    let pages = session.state().await;
+
     let embed = CreateEmbed::default()
         .title("Paginator Example")
         // this is safe! Index cannot go out of bounds
-        .description(state[idx])
+        .description(pages[idx])
         .color(poise::serenity_prelude::Color::BLUE)
         .apply_if(cancellation_type == CancellationType::Timeout, |embed| {
             embed.footer(CreateEmbedFooter::new("Cancelled due to timeout"))
@@ -41,20 +44,31 @@ async fn page_generator(
     Ok(embed)
 }
 
+async fn handle_custom_event(
+    _ctx: Context<'_>,
+    event: SimpleEvent,
+    _idx: usize,
+    _session: Session<Vec<&'static str>>,
+) -> Result<CustomAction, Error> {
+    match event {
+        SimpleEvent::Home => Ok(CustomAction::GoTo(0)),
+    }
+}
+
 #[command(slash_command)]
 pub async fn test_paginate(ctx: Context<'_>) -> Result<(), Error> {
-    let pages: Arc<[&'static str]> = [
+    let pages = vec![
         "Page 1: Welcome to the paginator example!",
         "Page 2: This is the second page.",
         "Page 3: Here is the third page.",
         "Page 4: And this is the fourth page.",
         "Page 5: Finally, we have reached the last page.",
-    ]
-    .into();
+    ];
 
-    custom_paginate::<'_, PaginationInformation, _, _>(
+    custom_paginate::<'_, PaginationInformation, _, _, _>(
         ctx,
         page_generator,
+        handle_custom_event,
         pages.len(),
         Duration::from_secs(60),
         pages,
@@ -3,25 +3,38 @@ use {
     poise::{
         ApplicationContext,
         serenity_prelude::{
-            self, ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, ReactionType,
+            ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, ReactionType,
         },
     },
-    poise_paginator::{Event, View},
+    poise_paginator::{Event, Interactions, View},
     std::sync::Arc,
-    tokio::sync::mpsc::UnboundedSender,
+    tokio::sync::mpsc::Sender,
 };
 
 pub struct SimpleView;
 
-impl View<Data, Error> for SimpleView {
-    fn create_ids(ctx: ApplicationContext<'_, Data, Error>) -> Arc<[String]> {
+/// The custom events sent by [SimpleView].
+pub enum SimpleEvent {
+    Home,
+}
+
+impl SimpleView {
+    /// Creates the component ids for the session with the given id.
+    pub fn ids(id: u64) -> Arc<[String]> {
         [
-            format!("{}_rewind", ctx.id()),
-            format!("{}_counter", ctx.id()),
-            format!("{}_forward", ctx.id()),
+            format!("{id}_rewind"),
+            format!("{id}_counter"),
+            format!("{id}_forward"),
+            format!("{id}_home"),
         ]
         .into()
     }
+}
+
+impl View<Data, Error, SimpleEvent> for SimpleView {
+    fn create_ids(ctx: ApplicationContext<'_, Data, Error>) -> Arc<[String]> {
+        Self::ids(ctx.id())
+    }
 
     fn rerender_components(
         ids: Arc<[String]>,
@@ -30,7 +43,7 @@ impl View<Data, Error> for SimpleView {
         disable_all: bool,
     ) -> Vec<CreateActionRow> {
         let (left_disabled, right_disabled) = match (disable_all, current_idx, length) {
-            (true, ..) => (true, true),
+            (true, ..) | (false, 0, 1) => (true, true),
             (false, 0, _) => (true, false),
             (false, idx, len) if idx == len - 1 => (false, true),
             (false, ..) => (false, false),
@@ -48,23 +61,33 @@ impl View<Data, Error> for SimpleView {
                 .emoji(ReactionType::Unicode("▶️".to_owned()))
                 .style(ButtonStyle::Secondary)
                 .disabled(right_disabled),
+            CreateButton::new(&ids[3])
+                .emoji(ReactionType::Unicode("🏠".to_owned()))
+                .style(ButtonStyle::Primary)
+                .disabled(disable_all),
         ])]
     }
 
     async fn on_button_press(
-        _ctx: serenity_prelude::Context,
+        _interactions: Arc<dyn Interactions>,
         press: ComponentInteraction,
-        tx: UnboundedSender<Event<Error>>,
+        tx: Sender<Event<Error, SimpleEvent>>,
         ids: Arc<[String]>,
     ) -> Result<(), Error> {
         match press.data.custom_id.as_str() {
             id if id == ids[0] => {
                 // Rewind
-                tx.send(Event::Previous(press)).unwrap_or_default();
+                tx.send(Event::Previous(press)).await.unwrap_or_default();
             }
             id if id == ids[2] => {
                 // Forward
-                tx.send(Event::Next(press)).unwrap_or_default();
+                tx.send(Event::Next(press)).await.unwrap_or_default();
+            }
+            id if id == ids[3] => {
+                // Home
+                tx.send(Event::Custom(press, SimpleEvent::Home))
+                    .await
+                    .unwrap_or_default();
             }
 
             _ => unreachable!("Unexpected button ID: {}", press.data.custom_id),
@@ -0,0 +1,14 @@
+use {
+    poise_paginator::check_view,
+    poise_paginator_example_custom::{
+        Data, Error,
+        view::{SimpleEvent, SimpleView},
+    },
+};
+
+#[tokio::test]
+async fn the_simple_view_conforms() {
+    check_view::<SimpleView, Data, Error, SimpleEvent>(SimpleView::ids)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,109 @@
+use {
+    discord_stand_in::Discord,
+    poise::serenity_prelude::CreateEmbed,
+    poise_paginator::{
+        CancellationType, CustomAction, HttpTransport, PaginationInfo, PaginatorOptions, Session,
+        Transport, custom_paginate_on,
+    },
+    poise_paginator_example_custom::{
+        Data, Error,
+        view::{SimpleEvent, SimpleView},
+    },
+    serde_json::{Value, json},
+    std::time::Duration,
+};
+
+struct Info;
+
+impl PaginationInfo for Info {
+    type View = SimpleView;
+    type PoiseData = Data;
+    type PoiseError = Error;
+    type CustomEvent = SimpleEvent;
+}
+
+/// The components of [SimpleView], as Discord receives them.
+fn components(
+    id: u64,
+    counter: &str,
+    left_disabled: bool,
+    right_disabled: bool,
+    disable_all: bool,
+) -> Value {
+    json!([{
+        "type": 1,
+        "components": [
+            { "type": 2, "custom_id": format!("{id}_rewind"), "style": 2, "disabled": left_disabled, "emoji": { "name": "◀️" } },
+            { "type": 2, "custom_id": format!("{id}_counter"), "style": 1, "disabled": true, "label": counter },
+            { "type": 2, "custom_id": format!("{id}_forward"), "style": 2, "disabled": right_disabled, "emoji": { "name": "▶️" } },
+            { "type": 2, "custom_id": format!("{id}_home"), "style": 1, "disabled": disable_all, "emoji": { "name": "🏠" } },
+        ],
+    }])
+}
+
+#[tokio::test]
+async fn the_home_button_goes_back_to_the_first_page() {
+    let discord = Discord::start().await;
+
+    let transport = HttpTransport::new(
+        discord.http.clone(),
+        discord.command(),
+        discord.dispatcher.clone(),
+    );
+
+    let id = transport.session_id();
+    let ids = SimpleView::ids(id);
+
+    let session = tokio::spawn(async move {
+        custom_paginate_on::<Info, _, _, _, _, _>(
+            (),
+            &transport,
+            ids,
+            |_, index, _, _session: Session<()>| async move {
+                Ok(CreateEmbed::new().title(format!("Page {}", index + 1)))
+            },
+            |_, SimpleEvent::Home, _, _| async { Ok(CustomAction::GoTo(0)) },
+            3,
+            PaginatorOptions::new(Duration::from_millis(500)),
+            (),
+        )
+        .await
+    });
+
+    let request = discord.next_request().await;
+    assert_eq!(
+        request.body["data"]["components"],
+        components(id, "1 / 3", true, false, false)
+    );
+    discord.next_request().await;
+
+    discord.press(&format!("{id}_forward")).await;
+    let request = discord.next_request().await;
+    assert_eq!(request.body["type"], 7);
+    assert_eq!(request.body["data"]["embeds"][0]["title"], "Page 2");
+    assert_eq!(
+        request.body["data"]["components"],
+        components(id, "2 / 3", false, false, false)
+    );
+
+    discord.press(&format!("{id}_home")).await;
+    let request = discord.next_request().await;
+    assert_eq!(
+        request.path,
+        "/api/v10/interactions/2/press-token-2/callback"
+    );
+    assert_eq!(request.body["data"]["embeds"][0]["title"], "Page 1");
+    assert_eq!(
+        request.body["data"]["components"],
+        components(id, "1 / 3", true, false, false)
+    );
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Timeout);
+
+    let request = discord.next_request().await;
+    assert_eq!(request.method, "PATCH");
+    assert_eq!(
+        request.body["components"],
+        components(id, "1 / 3", true, true, true)
+    );
+}
@@ -0,0 +1,49 @@
+//! Previews a paginator in the terminal, for trying out page layouts without Discord.
+//!
+//! Run it with `cargo run --features preview --bin preview`, and swap in your own generator.
+
+use {
+    poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter},
+    poise_paginator::{CancellationType, Session, preview},
+    std::time::Duration,
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+async fn page_generator(
+    _cx: (),
+    idx: usize,
+    cancellation_type: CancellationType,
+    session: Session<Vec<&'static str>>,
+) -> Result<CreateEmbed, Error> {
+    let pages = session.state().await;
+
+    let embed = CreateEmbed::new()
+        .title("Paginator Preview")
+        .description(pages[idx])
+        .field("Page", format!("{} of {}", idx + 1, pages.len()), true)
+        .field("Cancelled", format!("{cancellation_type:?}"), true)
+        .footer(CreateEmbedFooter::new("Previewed in the terminal"));
+
+    Ok(embed)
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Error> {
+    let pages = vec![
+        "Page 1: Welcome to the paginator preview!",
+        "Page 2: This is the second page.",
+        "Page 3: Here is the third page.",
+        "Page 4: And this is the fourth page.",
+        "Page 5: Finally, we have reached the last page.",
+    ];
+
+    preview(
+        (),
+        page_generator,
+        pages.len(),
+        Duration::from_secs(600),
+        pages,
+    )
+    .await
+}
@@ -1,5 +1,8 @@
+use crate::{Error, http::error_response};
+
 /// A type representing different states of cancellation for a paginator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum CancellationType {
     /// The cancellation was triggered by a timeout.
     Timeout,
@@ -7,6 +10,53 @@ pub enum CancellationType {
     /// The cancellation was triggered by the user pressing the cancel button.
     UserInput,
 
+    /// The cancellation was triggered through [PaginatorHandle::close](crate::PaginatorHandle::close).
+    Programmatic,
+
+    /// The cancellation was triggered by [SessionRegistry::shutdown](crate::SessionRegistry::shutdown)
+    /// or the [cancellation token](crate::PaginatorOptions::cancellation_token) of the session, usually because the bot is shutting down.
+    Shutdown,
+
+    /// The cancellation was triggered by a newer paginator of the same user or channel, exceeding the limit of a [SessionRegistry](crate::SessionRegistry).
+    Replaced,
+
+    /// The paginator message was deleted.
+    MessageDeleted,
+
+    /// The channel of the paginator was deleted.
+    ChannelDeleted,
+
+    /// The bot lost the permissions to view or edit the paginator message.
+    MissingPermissions,
+
+    /// The paginator message is ephemeral and its interaction token was about to expire.
+    ///
+    /// Ephemeral messages can only be edited through the token of the command interaction, which is valid for 15 minutes.
+    /// The session ends shortly before that, so the final page can still be rendered. Other messages are edited through the channel afterwards.
+    TokenExpired,
+
+    /// The paginator ended because of an error, e.g. in the generator. The error is returned from the paginator.
+    Error,
+
     /// The cancellation has not been triggered, the interaction is still ongoing.
     NotCancelled,
 }
+
+impl CancellationType {
+    /// Classifies errors that end a paginator for good, based on the error codes returned by Discord.
+    ///
+    /// Returns [CancellationType::Error] for any other error.
+    pub(crate) fn from_error(e: &Error) -> Self {
+        let Some(response) = error_response(e) else {
+            return Self::Error;
+        };
+
+        // https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+        match response.error.code {
+            10008 => Self::MessageDeleted,
+            10003 => Self::ChannelDeleted,
+            50001 | 50013 => Self::MissingPermissions,
+            _ => Self::Error,
+        }
+    }
+}
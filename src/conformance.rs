@@ -0,0 +1,254 @@
+use {
+    crate::{
+        Error, dispatcher,
+        event::Event,
+        transport::{Interactions, mock::MockTransport},
+        view::View,
+    },
+    serde_json::{Value, json},
+    std::{collections::HashSet, sync::Arc, time::Duration},
+    tokio::sync::mpsc,
+};
+
+/// The session id the component ids are created for.
+const SESSION_ID: u64 = 1_234_567_890;
+
+/// The lengths the components are rendered for: a single page, the smallest with both ends, and many pages.
+const LENGTHS: [usize; 3] = [1, 2, 1000];
+
+const MAX_ROWS: usize = 5;
+const MAX_COMPONENTS_PER_ROW: usize = 5;
+const MAX_LABEL_LENGTH: usize = 80;
+const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+/// How long a button press may take to be handled.
+const PRESS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Checks that a [View] works with the paginators and within Discord's limits, returning every violation at once.
+///
+/// [View::create_ids] needs a command context, so `ids` creates the component ids for a session id instead, like
+/// [DefaultView::ids](crate::DefaultView::ids). The checks are:
+/// - the ids are unique and start with the session id, so the [InteractionDispatcher](crate::InteractionDispatcher)
+///   can route them
+/// - the components stay within 5 action rows of 5 components each, with labels of at most 80 characters and
+///   custom ids of at most 100, for 1, 2 and 1000 pages
+/// - [View::on_button_press] handles every enabled component without panicking, failing or hanging
+/// - no enabled component goes back on the first page or forward on the last page, and all components are disabled
+///   once the paginator is cancelled or loading a page
+///
+/// Run it from an async test, e.g. `check_view::<MyView, Data, Error, MyEvent>(MyView::ids).await.unwrap()`.
+pub async fn check_view<V, D, E, C>(ids: impl Fn(u64) -> Arc<[String]>) -> Result<(), Error>
+where
+    V: View<D, E, C>,
+    E: Send + 'static,
+    C: Send + 'static,
+{
+    let mut violations = Vec::new();
+
+    let session_ids = ids(SESSION_ID);
+    check_ids(&session_ids, &mut violations);
+
+    for length in LENGTHS {
+        let mut indices = vec![0, length / 2, length - 1];
+        indices.dedup();
+
+        for index in indices {
+            let page = format!("page {} of {length}", index + 1);
+
+            for disable_all in [false, true] {
+                let rows = json!(V::rerender_components(
+                    session_ids.clone(),
+                    index,
+                    length,
+                    disable_all
+                ));
+
+                for component in check_rows(&rows, &page, &mut violations) {
+                    let Some(custom_id) = component["custom_id"].as_str() else {
+                        // Link buttons aren't pressed through interactions.
+                        continue;
+                    };
+
+                    if component["disabled"] == true {
+                        continue;
+                    }
+
+                    if disable_all {
+                        violations.push(format!(
+                            "{custom_id} is enabled on {page} after the paginator was cancelled"
+                        ));
+                        continue;
+                    }
+
+                    match press::<V, D, E, C>(session_ids.clone(), custom_id).await {
+                        Ok(Some(Direction::Back)) if index == 0 => violations
+                            .push(format!("{custom_id} is enabled on {page} but goes back")),
+                        Ok(Some(Direction::Forward)) if index == length - 1 => violations
+                            .push(format!("{custom_id} is enabled on {page} but goes forward")),
+                        Ok(_) => {}
+                        Err(e) => violations.push(format!("pressing {custom_id} on {page} {e}")),
+                    }
+                }
+            }
+
+            let rows = json!(V::loading_components(session_ids.clone(), index, length));
+
+            for component in check_rows(&rows, &page, &mut violations) {
+                if let Some(custom_id) = component["custom_id"].as_str()
+                    && component["disabled"] != true
+                {
+                    violations.push(format!("{custom_id} is enabled while {page} is loading"));
+                }
+            }
+        }
+    }
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    violations.dedup();
+
+    Err(Error::from(format!(
+        "The view violates {} check(s):\n- {}",
+        violations.len(),
+        violations.join("\n- ")
+    )))
+}
+
+/// Which way a button press moves the paginator.
+enum Direction {
+    Back,
+    Forward,
+}
+
+fn check_ids(ids: &[String], violations: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+
+    for id in ids {
+        if !seen.insert(id) {
+            violations.push(format!("the id {id} is created more than once"));
+        }
+
+        if dispatcher::session_id(id) != Some(SESSION_ID) {
+            violations.push(format!(
+                "the id {id} doesn't start with the session id {SESSION_ID}"
+            ));
+        }
+    }
+}
+
+/// Checks the limits of the action rows, returning their components.
+fn check_rows<'a>(rows: &'a Value, page: &str, violations: &mut Vec<String>) -> Vec<&'a Value> {
+    let rows = rows.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    if rows.len() > MAX_ROWS {
+        violations.push(format!(
+            "{page} has {} action rows, at most {MAX_ROWS} are allowed",
+            rows.len()
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut all = Vec::new();
+
+    for (row_index, row) in rows.iter().enumerate() {
+        let row_number = row_index + 1;
+        let components = row["components"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        if components.is_empty() {
+            violations.push(format!("row {row_number} on {page} is empty"));
+        }
+
+        if components.len() > MAX_COMPONENTS_PER_ROW {
+            violations.push(format!(
+                "row {row_number} on {page} has {} components, at most {MAX_COMPONENTS_PER_ROW} are allowed",
+                components.len()
+            ));
+        }
+
+        // Select menus take up a whole row.
+        if components.len() > 1 && components.iter().any(|component| component["type"] != 2) {
+            violations.push(format!(
+                "row {row_number} on {page} has a select menu next to other components"
+            ));
+        }
+
+        for component in components {
+            if let Some(label) = component["label"].as_str()
+                && label.chars().count() > MAX_LABEL_LENGTH
+            {
+                violations.push(format!(
+                    "the label {label:?} on {page} is longer than {MAX_LABEL_LENGTH} characters"
+                ));
+            }
+
+            if let Some(custom_id) = component["custom_id"].as_str() {
+                if custom_id.chars().count() > MAX_CUSTOM_ID_LENGTH {
+                    violations.push(format!(
+                        "the id {custom_id} on {page} is longer than {MAX_CUSTOM_ID_LENGTH} characters"
+                    ));
+                }
+
+                if !seen.insert(custom_id) {
+                    violations.push(format!(
+                        "the id {custom_id} is rendered more than once on {page}"
+                    ));
+                }
+
+                if dispatcher::session_id(custom_id) != Some(SESSION_ID) {
+                    violations.push(format!(
+                        "the id {custom_id} on {page} doesn't start with the session id {SESSION_ID}"
+                    ));
+                }
+            }
+
+            all.push(component);
+        }
+    }
+
+    all
+}
+
+/// Presses the component with the given id, returning which way the resulting event moves the paginator.
+async fn press<V, D, E, C>(ids: Arc<[String]>, custom_id: &str) -> Result<Option<Direction>, String>
+where
+    V: View<D, E, C>,
+    E: Send + 'static,
+    C: Send + 'static,
+{
+    let transport = MockTransport::new();
+
+    // Modals opened by the press are dismissed right away.
+    transport.dismiss_modal();
+
+    let press = crate::transport::mock::interaction(1, custom_id)
+        .map_err(|e| format!("can't be pressed: {e}"))?;
+
+    let (tx, mut rx) = mpsc::channel(16);
+    let handled = tokio::spawn(V::on_button_press(
+        Arc::new(transport) as Arc<dyn Interactions>,
+        press,
+        tx,
+        ids,
+    ));
+
+    match tokio::time::timeout(PRESS_TIMEOUT, handled).await {
+        Err(_) => return Err(format!("isn't handled within {PRESS_TIMEOUT:?}")),
+        Ok(Err(e)) if e.is_panic() => return Err("panics".to_owned()),
+        Ok(Err(e)) => return Err(format!("isn't handled: {e}")),
+        Ok(Ok(Err(e))) => return Err(format!("fails: {e}")),
+        Ok(Ok(Ok(()))) => {}
+    }
+
+    let direction = match rx.try_recv() {
+        Ok(Event::ToStart(_) | Event::Previous(_)) => Some(Direction::Back),
+        Ok(Event::Next(_) | Event::ToEnd(_)) => Some(Direction::Forward),
+        _ => None,
+    };
+
+    Ok(direction)
+}
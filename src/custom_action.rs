@@ -0,0 +1,17 @@
+/// What the paginator should do after a custom event has been handled.
+///
+/// Returned by the custom event handler passed to [custom_paginate](crate::custom_paginate).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CustomAction {
+    /// Acknowledge the interaction without touching the message.
+    None,
+
+    /// Re-run the generator for the current page and update the message.
+    Rerender,
+
+    /// Go to the given page and update the message. Indices past the last page are clamped to the last page.
+    GoTo(usize),
+
+    /// End the session, rendering the current page with [CancellationType::UserInput](crate::CancellationType::UserInput).
+    End,
+}
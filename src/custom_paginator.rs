@@ -1,15 +1,17 @@
 use {
-    crate::{Error, cancellation_type::CancellationType, event::Event},
-    poise::{
-        CreateReply,
-        serenity_prelude::{
-            self, ChannelId, Color, ComponentInteraction, ComponentInteractionCollector,
-            CreateEmbed, CreateInteractionResponse, CreateInteractionResponseFollowup,
-            CreateInteractionResponseMessage, UserId, futures::StreamExt,
-        },
+    crate::{
+        Error,
+        cancellation_type::CancellationType,
+        custom_action::CustomAction,
+        dispatcher::InteractionDispatcher,
+        options::PaginatorOptions,
+        session::{self, Session},
+        store::SessionRecord,
+        transport::{Transport, discord::DiscordTransport, resumed::ResumedTransport},
     },
-    std::{fmt::Display, sync::Arc, time::Duration},
-    tokio::sync::mpsc::UnboundedSender,
+    poise::serenity_prelude::{CreateEmbed, Http},
+    serde::de::DeserializeOwned,
+    std::{fmt::Display, sync::Arc},
 };
 
 pub use crate::view::View;
@@ -18,7 +20,9 @@ pub trait PaginationInfo {
     type PoiseData: Send + Sync + 'static;
     type PoiseError: Sized + Send + Sync + 'static + Display;
 
-    type View: View<Self::PoiseData, Self::PoiseError> + Send + Sync + 'static;
+    type CustomEvent: Send + 'static;
+
+    type View: View<Self::PoiseData, Self::PoiseError, Self::CustomEvent> + Send + Sync + 'static;
 }
 
 /// A paginator function that allows users to navigate through a series of pages with a very fancy UI.
@@ -27,237 +31,141 @@ pub trait PaginationInfo {
 ///
 /// It is called at the very beginning, when the paginator is created, and every time a button is pressed.
 /// It's also called when the pagination is cancelled through user input (the cancel button) or due to a timeout, represented by the [CancellationType](crate::cancellation_type::CancellationType).
+/// If the user navigates further while a page is rendered, the call is cancelled and the newer page is rendered instead.
 ///
 /// This function propagates the Context to the generator function, allowing you to access the context of the command.
-/// It also allows you to pass some state to the generator function, which can be used to store additional information across pages. Note that this state is cloned for each page.
+/// It also allows you to pass some state to the generator function, which can be used to store additional information across pages. The state is never cloned, the generator receives a [Session](crate::Session) handle through which it can be read and mutated, and changes persist across pages.
+///
+/// Buttons of the view can send [Event::Custom](crate::Event::Custom) events, which are passed to the handler along with the current index and the session, through which the state can be mutated.
+/// The returned [CustomAction] decides whether the page is re-rendered, changed, or the session is ended.
 ///
 /// Due to how buttons are handled, the index cannot go out of bounds (that is below 0 or above the length of the pages).
+/// This also holds when the length changes, the index is clamped to the last page in that case.
+///
+/// Returns the [CancellationType] the session ended with. If the message or its channel was deleted, or the bot lost access to it,
+/// the session ends with the matching variant instead of an error. Other errors end the session and are returned.
 ///
 /// # Arguments
 /// * `ctx` - The context of the command.
 /// * `generator` - A function that generates the embed for the current page.
-/// * `length` - The total number of pages.
-/// * `timeout` - The duration after which the pagination will be cancelled if no interaction occurs.
-/// * `state` - A state that can be used to store additional information across pages, accessed through the generator.
-pub async fn paginate<'a, P, Fut, S>(
+/// * `handler` - A function that handles the [custom events](crate::Event::Custom) sent by the view, returning the [CustomAction] to take.
+/// * `length` - The initial number of pages, which can be changed during the session through [Session::set_length](crate::Session::set_length).
+/// * `options` - The [PaginatorOptions](crate::PaginatorOptions) of the session. Passing a [Duration](std::time::Duration) sets the duration after which the pagination will be cancelled if no interaction occurs.
+/// * `state` - A state that can be used to store additional information across pages, accessed through the [Session](crate::Session) handle.
+pub async fn paginate<'a, P, Fut, HandlerFut, S>(
     ctx: poise::ApplicationContext<'a, P::PoiseData, P::PoiseError>,
     generator: impl Fn(
         poise::ApplicationContext<'a, P::PoiseData, P::PoiseError>,
         usize,
         CancellationType,
-        S,
+        Session<S>,
     ) -> Fut,
+    handler: impl Fn(
+        poise::ApplicationContext<'a, P::PoiseData, P::PoiseError>,
+        P::CustomEvent,
+        usize,
+        Session<S>,
+    ) -> HandlerFut,
     length: usize,
-    timeout: Duration,
+    options: impl Into<PaginatorOptions<S>>,
     state: S,
-) -> Result<(), Error>
+) -> Result<CancellationType, Error>
 where
     P: PaginationInfo,
-    S: Clone + Send + Sync + 'static,
+    S: Send + 'static,
     Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    HandlerFut: Future<Output = Result<CustomAction, Error>> + Send,
 {
-    let id = ctx.id();
-
-    let mut current_idx: usize = 0;
+    let options = options.into();
+    let transport = DiscordTransport::new(ctx, options.dispatcher.clone());
 
-    let ids = P::View::create_ids(ctx);
-
-    let components = P::View::rerender_components(Arc::clone(&ids), current_idx, length, false);
-
-    let first_embed = generator(
+    session::run::<_, _, _, P::View, P::PoiseError, P::CustomEvent, _, _, _, _, _>(
         ctx,
-        current_idx,
-        CancellationType::NotCancelled,
-        state.clone(),
+        &transport,
+        P::View::create_ids(ctx),
+        generator,
+        handler,
+        length,
+        0,
+        options,
+        state,
     )
-    .await?;
-
-    let msg = ctx
-        .send(
-            CreateReply::default()
-                .embed(first_embed)
-                .components(components),
-        )
-        .await?;
-
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Event<P::PoiseError>>();
-
-    tokio::spawn(
-        handle_button_presses::<P::PoiseData, P::PoiseError, P::View>(
-            ctx.serenity_context().clone(),
-            tx,
-            id.to_string(),
-            ctx.author().id,
-            ctx.channel_id(),
-            timeout,
-            Arc::clone(&ids),
-        ),
-    );
-
-    while let Some(event) = rx.recv().await {
-        // This is a flag that is solely there for the "Jump to page" button.
-        // It is a flag to indicate whether to use a followup response or not,
-        // because the first one was used to create the modal.
-        let mut interaction_already_responded = false;
-
-        let interaction = match event {
-            Event::ToStart(interaction) => {
-                current_idx = 0;
-                interaction
-            }
-            Event::Previous(interaction) => {
-                current_idx = current_idx.saturating_sub(1);
-                interaction
-            }
-
-            Event::Next(interaction) => {
-                current_idx += 1;
-                interaction
-            }
-            Event::ToEnd(interaction) => {
-                current_idx = length - 1;
-                interaction
-            }
-
-            Event::Jump(interaction, page) => {
-                if page < length {
-                    current_idx = page;
-                    interaction_already_responded = true;
-                    interaction
-                } else {
-                    send_error_embed(
-                        ctx,
-                        interaction,
-                        format!("Page {} does not exist.", page + 1),
-                    )
-                    .await?;
-                    continue;
-                }
-            }
-
-            Event::CancelledByTimeout => {
-                msg.edit(
-                    ctx.into(),
-                    CreateReply::default()
-                        .embed(
-                            generator(ctx, current_idx, CancellationType::Timeout, state.clone())
-                                .await?,
-                        )
-                        .components(P::View::rerender_components(
-                            Arc::clone(&ids),
-                            current_idx,
-                            length,
-                            true,
-                        )),
-                )
-                .await?;
-                break;
-            }
-
-            Event::CancelledByUser(interaction) => {
-                let reply = CreateInteractionResponseMessage::default()
-                    .embed(
-                        generator(ctx, current_idx, CancellationType::UserInput, state.clone())
-                            .await?,
-                    )
-                    .components(P::View::rerender_components(
-                        Arc::clone(&ids),
-                        current_idx,
-                        length,
-                        true,
-                    ));
-
-                interaction
-                    .create_response(ctx, CreateInteractionResponse::UpdateMessage(reply))
-                    .await?;
-
-                break;
-            }
-
-            Event::Error(interaction, e) => {
-                send_error_embed(ctx, interaction, e).await?;
-                continue;
-            }
-        };
-
-        let embed = generator(
-            ctx,
-            current_idx,
-            CancellationType::NotCancelled,
-            state.clone(),
-        )
-        .await?;
-        let components = P::View::rerender_components(Arc::clone(&ids), current_idx, length, false);
-
-        match interaction_already_responded {
-            true => {
-                msg.edit(
-                    ctx.into(),
-                    CreateReply::default().embed(embed).components(components),
-                )
-                .await?;
-            }
-            false => {
-                let reply = CreateInteractionResponseMessage::default()
-                    .embed(embed)
-                    .components(components);
-
-                interaction
-                    .create_response(ctx, CreateInteractionResponse::UpdateMessage(reply))
-                    .await?;
-            }
-        };
-    }
-
-    Ok(())
+    .await
 }
 
-async fn handle_button_presses<D, E, V: View<D, E>>(
-    ctx: serenity_prelude::Context,
-    tx: UnboundedSender<Event<E>>,
-    id: String,
-    author_id: UserId,
-    channel_id: ChannelId,
-    timeout: Duration,
+/// Like [paginate], but running the session on the given [Transport] instead of a command, e.g. on a [MockTransport](crate::MockTransport) in tests.
+///
+/// The generator and the handler receive `cx` instead of the command context, which can be anything that is cheap to copy.
+/// Since there is no command context to create them from, the component ids are passed in as well,
+/// as [View::create_ids] would create them for the [session id](Transport::session_id).
+#[allow(clippy::too_many_arguments)]
+pub async fn paginate_on<P, X, T, Fut, HandlerFut, S>(
+    cx: X,
+    transport: &T,
     ids: Arc<[String]>,
-) -> Result<(), Error> {
-    let mut collector = ComponentInteractionCollector::new(&ctx)
-        .author_id(author_id)
-        .channel_id(channel_id)
-        .timeout(timeout)
-        .filter(move |interaction| interaction.data.custom_id.starts_with(&id))
-        .stream();
-
-    while let Some(press) = collector.next().await {
-        V::on_button_press(ctx.clone(), press, tx.clone(), Arc::clone(&ids)).await?;
-    }
-
-    tx.send(Event::CancelledByTimeout).unwrap_or_default();
-    Ok(())
+    generator: impl Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    handler: impl Fn(X, P::CustomEvent, usize, Session<S>) -> HandlerFut,
+    length: usize,
+    options: impl Into<PaginatorOptions<S>>,
+    state: S,
+) -> Result<CancellationType, Error>
+where
+    P: PaginationInfo,
+    X: Copy,
+    T: Transport,
+    S: Send + 'static,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    HandlerFut: Future<Output = Result<CustomAction, Error>> + Send,
+{
+    session::run::<_, _, _, P::View, P::PoiseError, P::CustomEvent, _, _, _, _, _>(
+        cx,
+        transport,
+        ids,
+        generator,
+        handler,
+        length,
+        0,
+        options.into(),
+        state,
+    )
+    .await
 }
 
-async fn send_error_embed<D, E>(
-    ctx: poise::ApplicationContext<'_, D, E>,
-    interaction: ComponentInteraction,
-    description: impl Display,
-) -> Result<(), Error>
+/// Resumes a session of [paginate] from its [SessionRecord], like [resume](crate::resume) does for the default view.
+///
+/// The component ids are passed in, as [View::create_ids] created them for the [session id](SessionRecord::session_id).
+#[allow(clippy::too_many_arguments)]
+pub async fn resume<P, X, Fut, HandlerFut, S>(
+    cx: X,
+    http: Arc<Http>,
+    dispatcher: InteractionDispatcher,
+    record: SessionRecord,
+    ids: Arc<[String]>,
+    generator: impl Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    handler: impl Fn(X, P::CustomEvent, usize, Session<S>) -> HandlerFut,
+    options: impl Into<PaginatorOptions<S>>,
+) -> Result<CancellationType, Error>
 where
-    D: Send + Sync + 'static,
-    E: Send + Sync + 'static,
+    P: PaginationInfo,
+    X: Copy,
+    S: DeserializeOwned + Send + 'static,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    HandlerFut: Future<Output = Result<CustomAction, Error>> + Send,
 {
-    let embed = CreateEmbed::new()
-        .title("Error")
-        .description(description.to_string())
-        .color(Color::RED);
-
-    interaction
-        .create_followup(
-            ctx,
-            CreateInteractionResponseFollowup::default()
-                .embed(embed)
-                .ephemeral(true),
-        )
-        .await?;
-
-    Ok(())
+    let state = record.state()?;
+    let (length, index) = (record.length, record.index);
+    let transport = ResumedTransport::new(http, record, dispatcher);
+
+    session::run::<_, _, _, P::View, P::PoiseError, P::CustomEvent, _, _, _, _, _>(
+        cx,
+        &transport,
+        ids,
+        generator,
+        handler,
+        length,
+        index,
+        options.into(),
+        state,
+    )
+    .await
 }
@@ -0,0 +1,132 @@
+use {
+    poise::serenity_prelude::{ChannelId, ComponentInteraction, FullEvent, Interaction, UserId},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, MutexGuard},
+    },
+    tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender},
+};
+
+/// Routes component interactions to the paginator sessions they belong to.
+///
+/// Without a dispatcher, every session collects its button presses through its own collector,
+/// so every component interaction the bot receives runs through the filter of every open session.
+/// A dispatcher looks up the session by the id its component ids start with instead, so the cost of routing
+/// an interaction doesn't grow with the number of open sessions.
+///
+/// Paginators use it when it is passed through [PaginatorOptions::dispatcher](crate::PaginatorOptions::dispatcher),
+/// see the [crate-level setup](crate#setup) for where to keep it.
+#[derive(Clone, Default)]
+pub struct InteractionDispatcher {
+    routes: Arc<Mutex<HashMap<u64, Route>>>,
+}
+
+struct Route {
+    owner: UserId,
+    channel_id: ChannelId,
+    presses: UnboundedSender<ComponentInteraction>,
+}
+
+impl InteractionDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes button presses to their session. Call this from the poise `event_handler` for every event.
+    ///
+    /// Returns whether the event was routed to a session. Like with the collectors, presses by other users than the owner are ignored.
+    pub fn handle_event(&self, event: &FullEvent) -> bool {
+        let FullEvent::InteractionCreate {
+            interaction: Interaction::Component(press),
+        } = event
+        else {
+            return false;
+        };
+
+        let Some(session_id) = session_id(&press.data.custom_id) else {
+            return false;
+        };
+
+        match self.routes().get(&session_id) {
+            Some(route) if route.owner == press.user.id && route.channel_id == press.channel_id => {
+                route.presses.send(press.clone()).is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    /// The number of sessions receiving their button presses through the dispatcher.
+    pub fn count(&self) -> usize {
+        self.routes().len()
+    }
+
+    /// Routes the button presses on components whose ids start with the given session id to the returned subscription,
+    /// until it is dropped. Subscribing again with the same session id replaces the route. Paginators subscribe on their own, this is useful for other components sharing the dispatcher.
+    pub fn subscribe(&self, session_id: u64, owner: UserId, channel_id: ChannelId) -> Subscription {
+        let (presses, receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        self.routes().insert(
+            session_id,
+            Route {
+                owner,
+                channel_id,
+                presses,
+            },
+        );
+
+        Subscription {
+            routes: Arc::clone(&self.routes),
+            session_id,
+            receiver,
+        }
+    }
+
+    fn routes(&self) -> MutexGuard<'_, HashMap<u64, Route>> {
+        lock(&self.routes)
+    }
+}
+
+/// The session id a component id starts with, i.e. its leading digits.
+pub(crate) fn session_id(custom_id: &str) -> Option<u64> {
+    let end = custom_id
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(custom_id.len());
+
+    custom_id[..end].parse().ok()
+}
+
+fn lock(routes: &Mutex<HashMap<u64, Route>>) -> MutexGuard<'_, HashMap<u64, Route>> {
+    routes
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The button presses of a session, which is removed from its [InteractionDispatcher] when dropped.
+pub struct Subscription {
+    routes: Arc<Mutex<HashMap<u64, Route>>>,
+    session_id: u64,
+    receiver: UnboundedReceiver<ComponentInteraction>,
+}
+
+impl Subscription {
+    /// Receives the next button press.
+    pub async fn next(&mut self) -> Option<ComponentInteraction> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.receiver.close();
+
+        let mut routes = lock(&self.routes);
+
+        // The session may have subscribed again since, in which case the route belongs to the newer subscription.
+        if routes
+            .get(&self.session_id)
+            .is_some_and(|route| route.presses.is_closed())
+        {
+            routes.remove(&self.session_id);
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use poise::serenity_prelude::ComponentInteraction;
 
 #[derive(Debug, Clone)]
-pub enum Event<E> {
+pub enum Event<E, C = ()> {
     ToStart(ComponentInteraction),
     Next(ComponentInteraction),
     Previous(ComponentInteraction),
     ToEnd(ComponentInteraction),
     Jump(ComponentInteraction, usize),
+    Refresh(ComponentInteraction),
+    Custom(ComponentInteraction, C),
     CancelledByTimeout,
     CancelledByUser(ComponentInteraction),
     Error(ComponentInteraction, E),
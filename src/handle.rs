@@ -0,0 +1,115 @@
+use {
+    crate::cancellation_type::CancellationType,
+    std::sync::{Arc, Mutex},
+    tokio::sync::{
+        broadcast,
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+    },
+};
+
+/// The capacity of the navigation event channel. Subscribers lagging behind by more events miss the oldest ones.
+const EVENT_CAPACITY: usize = 32;
+
+/// A handle to control a paginator session from outside of it, e.g. from another task.
+///
+/// Pass it to the paginator through [PaginatorOptions::handle](crate::PaginatorOptions::handle).
+/// A handle controls a single session, cloning it is cheap and all clones control the same session.
+/// Commands sent before the session started are applied once it is running.
+#[derive(Clone)]
+pub struct PaginatorHandle {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    commands: UnboundedSender<Command>,
+    receiver: Mutex<Option<UnboundedReceiver<Command>>>,
+    events: broadcast::Sender<NavigationEvent>,
+}
+
+/// A command sent to a running session through a [PaginatorHandle].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Command {
+    Jump(usize),
+    Refresh,
+    SetLength(usize),
+    Close(CancellationType),
+}
+
+/// An event emitted by a paginator session, received through [PaginatorHandle::subscribe].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavigationEvent {
+    /// The message was updated to show the page at `index`, out of `length` pages.
+    Rendered { index: usize, length: usize },
+
+    /// The session ended.
+    Ended(CancellationType),
+}
+
+impl PaginatorHandle {
+    /// Creates a handle that is not attached to any session yet.
+    pub fn new() -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+
+        Self {
+            shared: Arc::new(Shared {
+                commands,
+                receiver: Mutex::new(Some(receiver)),
+                events,
+            }),
+        }
+    }
+
+    /// Jumps to the given page. Indices past the last page are clamped to the last page.
+    pub fn jump(&self, page: usize) {
+        self.send(Command::Jump(page));
+    }
+
+    /// Re-runs the generator for the current page and updates the message.
+    pub fn refresh(&self) {
+        self.send(Command::Refresh);
+    }
+
+    /// Changes the number of pages, see [Session::set_length](crate::Session::set_length).
+    pub fn set_length(&self, length: usize) {
+        self.send(Command::SetLength(length));
+    }
+
+    /// Ends the session, rendering the current page with [CancellationType::Programmatic].
+    pub fn close(&self) {
+        self.close_with(CancellationType::Programmatic);
+    }
+
+    pub(crate) fn close_with(&self, cancellation_type: CancellationType) {
+        self.send(Command::Close(cancellation_type));
+    }
+
+    /// Subscribes to the navigation events of the session.
+    pub fn subscribe(&self) -> broadcast::Receiver<NavigationEvent> {
+        self.shared.events.subscribe()
+    }
+
+    fn send(&self, command: Command) {
+        self.shared.commands.send(command).unwrap_or_default();
+    }
+
+    /// Takes the command receiver, which is only handed out to the first session using this handle.
+    pub(crate) fn take_receiver(&self) -> Option<UnboundedReceiver<Command>> {
+        self.shared
+            .receiver
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
+    pub(crate) fn emit(&self, event: NavigationEvent) {
+        // Sending only fails if there are no subscribers, which is fine.
+        self.shared.events.send(event).ok();
+    }
+}
+
+impl Default for PaginatorHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,107 @@
+use {
+    crate::Error,
+    poise::serenity_prelude::{self, ErrorResponse, HttpError},
+    std::time::Duration,
+};
+
+/// How the requests of a paginator session are retried after a transient failure,
+/// i.e. a rate limit, a server error or a connection problem.
+///
+/// Rate limits are waited out by serenity's ratelimiter, which reads the `retry-after` of the response and retries on its own.
+/// Serenity doesn't keep that delay in the errors it returns, so rate limits that still surface here, because the response
+/// had no `retry-after` or the ratelimiter of the `Http` is disabled, are retried with an exponential backoff like the
+/// other transient failures.
+///
+/// Defaults to 3 retries, with a backoff starting at 500 milliseconds and capped at 8 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy retrying a failed request up to `max_retries` times.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// A policy that never retries, ending the session on the first failure.
+    pub fn none() -> Self {
+        Self::new(0)
+    }
+
+    /// Sets the backoff before the first retry, which doubles with every further retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum backoff between two retries.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sends the request, retrying it as long as it fails transiently and retries are left.
+    pub(crate) async fn run<T, Fut>(&self, mut request: impl FnMut() -> Fut) -> Result<T, Error>
+    where
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut backoff = self.initial_backoff;
+
+        for _ in 0..self.max_retries {
+            match request().await {
+                Err(e) if is_transient(&e) => {
+                    tracing::debug!("Retrying a paginator request in {backoff:?}: {e}");
+
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.max_backoff);
+                }
+                result => return result,
+            }
+        }
+
+        request().await
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+/// The response of Discord, if the error was caused by an unsuccessful request.
+pub(crate) fn error_response(e: &Error) -> Option<&ErrorResponse> {
+    match e.downcast_ref::<serenity_prelude::Error>()? {
+        serenity_prelude::Error::Http(HttpError::UnsuccessfulRequest(response)) => Some(response),
+        _ => None,
+    }
+}
+
+/// Whether the request failed because the interaction can't be responded to anymore,
+/// either because its response window has passed or because it was already acknowledged.
+///
+/// https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+pub(crate) fn is_expired_interaction(e: &Error) -> bool {
+    error_response(e).is_some_and(|response| matches!(response.error.code, 10062 | 40060))
+}
+
+fn is_transient(e: &Error) -> bool {
+    if let Some(response) = error_response(e) {
+        return response.status_code.as_u16() == 429 || response.status_code.is_server_error();
+    }
+
+    matches!(
+        e.downcast_ref::<serenity_prelude::Error>(),
+        Some(serenity_prelude::Error::Http(HttpError::Request(e))) if e.is_timeout() || e.is_connect()
+    )
+}
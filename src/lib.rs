@@ -1,13 +1,68 @@
+//! Paginators for poise slash commands, showing one embed per page with buttons to navigate between them.
+//!
+//! # Setup
+//!
+//! A few types coordinate all paginators of the bot: the [InteractionDispatcher], the [SessionRegistry] and the
+//! [PersistentPaginators]. Create one of each you use at startup and keep it in your poise `Data` (or a static),
+//! so that every paginator gets the same one. Cloning them is cheap and all clones share the same state.
+//!
+//! The dispatcher and the persistent paginators handle button presses themselves, so call their `handle_event` from
+//! the poise `event_handler` for every event.
+
 mod cancellation_type;
+mod conformance;
+mod custom_action;
 mod custom_paginator;
+mod dispatcher;
 mod event;
+mod handle;
+mod http;
+mod limits;
+mod message;
+mod navigator;
+mod options;
 mod paginator;
+mod persistent;
+mod preview;
+mod registry;
+mod session;
+mod signer;
+mod snapshot;
+mod store;
+mod transport;
 mod view;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 
 pub use cancellation_type::CancellationType;
-pub use custom_paginator::{PaginationInfo, paginate as custom_paginate};
+pub use conformance::check_view;
+pub use custom_action::CustomAction;
+pub use custom_paginator::{
+    PaginationInfo, paginate as custom_paginate, paginate_on as custom_paginate_on,
+    resume as custom_resume,
+};
+pub use dispatcher::{InteractionDispatcher, Subscription};
 pub use event::Event;
-pub use paginator::paginate;
-pub use view::{View, default_view::DefaultView};
+pub use handle::{NavigationEvent, PaginatorHandle};
+pub use http::RetryPolicy;
+pub use navigator::{Navigation, Navigator};
+pub use options::PaginatorOptions;
+pub use paginator::{paginate, paginate_on, resume};
+pub use persistent::{PersistentId, PersistentPage, PersistentPaginators};
+#[cfg(feature = "preview")]
+pub use preview::{custom_preview, preview};
+pub use registry::SessionRegistry;
+pub use session::Session;
+pub use signer::Signer;
+pub use snapshot::{Snapshot, custom_snapshot, snapshot};
+pub use store::{FileSessionStore, MemorySessionStore, SessionRecord, SessionStore};
+pub use tokio_util::sync::CancellationToken;
+pub use transport::{
+    Interactions, Transport,
+    http::HttpTransport,
+    mock::{MockMessage, MockRequest, MockTransport},
+};
+pub use view::{
+    View,
+    default_view::{DefaultView, DefaultViewWithRefresh},
+};
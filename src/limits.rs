@@ -0,0 +1,228 @@
+use {
+    crate::Error,
+    poise::serenity_prelude::{CreateActionRow, CreateEmbed, Embed},
+    serde_json::Value,
+};
+
+// https://discord.com/developers/docs/resources/message#embed-object-embed-limits
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_FIELDS: usize = 25;
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+const MAX_FOOTER_LENGTH: usize = 2048;
+const MAX_AUTHOR_LENGTH: usize = 256;
+const MAX_TOTAL_LENGTH: usize = 6000;
+
+// https://discord.com/developers/docs/interactions/message-components
+const MAX_ROWS: usize = 5;
+const MAX_COMPONENTS_PER_ROW: usize = 5;
+const MAX_LABEL_LENGTH: usize = 80;
+
+const ELLIPSIS: &str = "…";
+
+/// Checks the page at the given index against Discord's limits before it is sent, so that exceeding them fails with an
+/// error naming what is too long instead of a rejected request.
+///
+/// With `truncate`, texts of the embed that are too long are cut off with an ellipsis and fields beyond the 25th are
+/// dropped. If the embed is still too long in total, the last fields are dropped and then the description is shortened.
+/// The components come from the view and are never truncated.
+pub(crate) fn check(
+    embed: CreateEmbed,
+    components: &[CreateActionRow],
+    index: usize,
+    truncate: bool,
+) -> Result<CreateEmbed, Error> {
+    check_components(&serde_json::to_value(components)?, index)?;
+
+    let mut json = serde_json::to_value(&embed)?;
+
+    if !truncate || !truncate_embed(&mut json) {
+        check_embed(&json, index)?;
+        return Ok(embed);
+    }
+
+    check_embed(&json, index)?;
+
+    Ok(CreateEmbed::from(serde_json::from_value::<Embed>(json)?))
+}
+
+/// The texts of the embed with a limit, as their JSON pointer, their name in errors and their limit.
+fn texts(embed: &Value) -> Vec<(String, String, usize)> {
+    let mut texts = vec![
+        (
+            "/title".to_owned(),
+            "The title".to_owned(),
+            MAX_TITLE_LENGTH,
+        ),
+        (
+            "/description".to_owned(),
+            "The description".to_owned(),
+            MAX_DESCRIPTION_LENGTH,
+        ),
+        (
+            "/author/name".to_owned(),
+            "The author name".to_owned(),
+            MAX_AUTHOR_LENGTH,
+        ),
+        (
+            "/footer/text".to_owned(),
+            "The footer text".to_owned(),
+            MAX_FOOTER_LENGTH,
+        ),
+    ];
+
+    let fields = embed["fields"].as_array().map(Vec::len).unwrap_or_default();
+
+    for i in 0..fields {
+        texts.push((
+            format!("/fields/{i}/name"),
+            format!("The name of field {}", i + 1),
+            MAX_FIELD_NAME_LENGTH,
+        ));
+        texts.push((
+            format!("/fields/{i}/value"),
+            format!("The value of field {}", i + 1),
+            MAX_FIELD_VALUE_LENGTH,
+        ));
+    }
+
+    texts
+}
+
+fn length(embed: &Value, pointer: &str) -> usize {
+    embed
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(|text| text.chars().count())
+        .unwrap_or_default()
+}
+
+/// The number of characters counting towards the total limit of an embed.
+fn total_length(embed: &Value) -> usize {
+    texts(embed)
+        .iter()
+        .map(|(pointer, ..)| length(embed, pointer))
+        .sum()
+}
+
+fn check_embed(embed: &Value, index: usize) -> Result<(), Error> {
+    let fields = embed["fields"].as_array().map(Vec::len).unwrap_or_default();
+
+    if fields > MAX_FIELDS {
+        return Err(Error::from(format!(
+            "The embed of the page at index {index} has {fields} fields, at most {MAX_FIELDS} are allowed"
+        )));
+    }
+
+    for (pointer, name, limit) in texts(embed) {
+        let length = length(embed, &pointer);
+
+        if length > limit {
+            return Err(Error::from(format!(
+                "{name} of the page at index {index} has {length} characters, at most {limit} are allowed"
+            )));
+        }
+    }
+
+    let total = total_length(embed);
+
+    if total > MAX_TOTAL_LENGTH {
+        return Err(Error::from(format!(
+            "The embed of the page at index {index} has {total} characters in total, at most {MAX_TOTAL_LENGTH} are allowed"
+        )));
+    }
+
+    Ok(())
+}
+
+fn check_components(rows: &Value, index: usize) -> Result<(), Error> {
+    let rows = rows.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    if rows.len() > MAX_ROWS {
+        return Err(Error::from(format!(
+            "The components of the page at index {index} have {} action rows, at most {MAX_ROWS} are allowed",
+            rows.len()
+        )));
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        let components = row["components"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        if components.len() > MAX_COMPONENTS_PER_ROW {
+            return Err(Error::from(format!(
+                "Action row {} of the page at index {index} has {} components, at most {MAX_COMPONENTS_PER_ROW} are allowed",
+                i + 1,
+                components.len()
+            )));
+        }
+
+        for component in components {
+            let length = length(component, "/label");
+
+            if length > MAX_LABEL_LENGTH {
+                return Err(Error::from(format!(
+                    "The label of {} on the page at index {index} has {length} characters, at most {MAX_LABEL_LENGTH} are allowed",
+                    component["custom_id"].as_str().unwrap_or("a button")
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Truncates the embed to its limits, returning whether anything changed.
+fn truncate_embed(embed: &mut Value) -> bool {
+    let mut changed = false;
+
+    if let Some(fields) = embed["fields"].as_array_mut()
+        && fields.len() > MAX_FIELDS
+    {
+        fields.truncate(MAX_FIELDS);
+        changed = true;
+    }
+
+    for (pointer, _, limit) in texts(embed) {
+        changed |= truncate_text(embed, &pointer, limit);
+    }
+
+    while total_length(embed) > MAX_TOTAL_LENGTH
+        && let Some(fields) = embed["fields"].as_array_mut()
+        && fields.pop().is_some()
+    {
+        changed = true;
+    }
+
+    let excess = total_length(embed).saturating_sub(MAX_TOTAL_LENGTH);
+
+    if excess > 0 {
+        let description = length(embed, "/description");
+        changed |= truncate_text(embed, "/description", description.saturating_sub(excess));
+    }
+
+    changed
+}
+
+/// Cuts off the text at the given pointer with an ellipsis, so that it has at most `limit` characters.
+fn truncate_text(embed: &mut Value, pointer: &str, limit: usize) -> bool {
+    let Some(text) = embed.pointer_mut(pointer) else {
+        return false;
+    };
+
+    let Some(string) = text.as_str() else {
+        return false;
+    };
+
+    if string.chars().count() <= limit {
+        return false;
+    }
+
+    let truncated: String = string.chars().take(limit.saturating_sub(1)).collect();
+    *text = Value::String(truncated + ELLIPSIS);
+
+    true
+}
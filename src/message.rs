@@ -0,0 +1,108 @@
+use {
+    crate::Error,
+    poise::{
+        ApplicationContext, CreateReply, ReplyHandle,
+        serenity_prelude::{
+            ChannelId, CreateActionRow, CreateEmbed, EditMessage, InteractionId, MessageFlags,
+            MessageId, Timestamp,
+        },
+    },
+    std::time::Duration,
+    tokio::time::Instant,
+};
+
+/// How long Discord accepts edits through the token of an interaction.
+const TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// The headroom before the token expires, covering clock skew and slow renders.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// The message of a paginator session.
+///
+/// It is edited through the token of the command interaction, which expires after 15 minutes.
+/// Afterwards, it is edited through the channel instead. Ephemeral messages can only be edited through the token,
+/// so sessions with an ephemeral message have to end before it expires.
+pub(crate) struct PaginatorMessage<'a> {
+    reply: ReplyHandle<'a>,
+    id: MessageId,
+    channel_id: ChannelId,
+    ephemeral: bool,
+    token_expires_at: Instant,
+}
+
+impl<'a> PaginatorMessage<'a> {
+    /// Sends the first page as the response to the command.
+    pub(crate) async fn send<D, E>(
+        ctx: ApplicationContext<'a, D, E>,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> Result<Self, Error> {
+        let token_expires_at = token_expires_at(ctx.interaction.id);
+
+        let reply = ctx
+            .send(CreateReply::default().embed(embed).components(components))
+            .await?;
+
+        let message = reply.message().await?;
+        let (id, channel_id) = (message.id, message.channel_id);
+        let ephemeral = message
+            .flags
+            .is_some_and(|flags| flags.contains(MessageFlags::EPHEMERAL));
+
+        Ok(Self {
+            reply,
+            id,
+            channel_id,
+            ephemeral,
+            token_expires_at,
+        })
+    }
+
+    pub(crate) fn id(&self) -> MessageId {
+        self.id
+    }
+
+    /// The point in time at which an ephemeral message can no longer be edited, or `None` if the message isn't ephemeral.
+    pub(crate) fn ephemeral_deadline(&self) -> Option<Instant> {
+        self.ephemeral.then_some(self.token_expires_at)
+    }
+
+    /// Edits the message through the token of the command interaction while it is valid, and through the channel afterwards.
+    pub(crate) async fn edit<D, E>(
+        &self,
+        ctx: ApplicationContext<'_, D, E>,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> Result<(), Error> {
+        if self.ephemeral || Instant::now() < self.token_expires_at {
+            self.reply
+                .edit(
+                    ctx.into(),
+                    CreateReply::default().embed(embed).components(components),
+                )
+                .await?;
+        } else {
+            self.channel_id
+                .edit_message(
+                    ctx.serenity_context(),
+                    self.id,
+                    EditMessage::new().embed(embed).components(components),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The point in time at which the token of the interaction can no longer be used, minus some headroom.
+pub(crate) fn token_expires_at(interaction_id: InteractionId) -> Instant {
+    // The token is as old as the interaction, which may have been deferred long before the paginator was created.
+    let age = Timestamp::now().unix_timestamp() - interaction_id.created_at().unix_timestamp();
+    let age = Duration::from_secs(age.max(0) as u64);
+
+    Instant::now()
+        + TOKEN_LIFETIME
+            .saturating_sub(age)
+            .saturating_sub(TOKEN_EXPIRY_MARGIN)
+}
@@ -0,0 +1,142 @@
+/// An action moving the position of a [Navigator].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Navigation {
+    /// Moves to the first page.
+    First,
+
+    /// Moves to the previous page.
+    Previous,
+
+    /// Moves to the next page.
+    Next,
+
+    /// Moves to the last page. Does nothing if the length is unknown.
+    Last,
+
+    /// Moves by the given number of pages, backwards if negative.
+    Step(isize),
+
+    /// Moves to the given page, or to the last page if it is out of bounds.
+    GoTo(usize),
+}
+
+/// The position of a paginator, moved through [Navigation]s.
+///
+/// It has no notion of Discord at all, so views and other paginators can reuse it to keep their index logic consistent with
+/// the paginators of this crate. The index is always within bounds, also when the length changes.
+///
+/// If the length is unknown, e.g. while streaming pages, the navigator can move forward indefinitely
+/// until the length is set through [Navigator::set_length].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Navigator {
+    index: usize,
+    length: Option<usize>,
+    wraparound: bool,
+}
+
+impl Navigator {
+    /// Creates a navigator on the first of `length` pages. There is always at least one page.
+    pub fn new(length: usize) -> Self {
+        Self {
+            index: 0,
+            length: Some(length.max(1)),
+            wraparound: false,
+        }
+    }
+
+    /// Creates a navigator on the first page, without a known number of pages.
+    pub fn unknown_length() -> Self {
+        Self {
+            index: 0,
+            length: None,
+            wraparound: false,
+        }
+    }
+
+    /// Moves to the given page, clamped to the last page.
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.apply(Navigation::GoTo(index));
+        self
+    }
+
+    /// Wraps around at the ends, so moving past the last page moves to the first page and vice versa.
+    /// Only applies while the length is known.
+    pub fn wraparound(mut self, wraparound: bool) -> Self {
+        self.wraparound = wraparound;
+        self
+    }
+
+    /// The index of the current page.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The number of pages, if known.
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// Changes the number of pages, clamping the index to the last page.
+    pub fn set_length(&mut self, length: Option<usize>) {
+        self.length = length.map(|length| length.max(1));
+        self.index = self.clamp(self.index);
+    }
+
+    /// Whether the given index is within bounds.
+    pub fn contains(&self, index: usize) -> bool {
+        self.length.is_none_or(|length| index < length)
+    }
+
+    /// Whether moving to the previous page changes the position.
+    pub fn has_previous(&self) -> bool {
+        self.index > 0 || (self.wraparound && self.length.is_some_and(|length| length > 1))
+    }
+
+    /// Whether moving to the next page changes the position.
+    pub fn has_next(&self) -> bool {
+        match self.length {
+            Some(length) => self.index + 1 < length || (self.wraparound && length > 1),
+            None => true,
+        }
+    }
+
+    /// Moves to the page at the given index if it exists, returning whether it does.
+    ///
+    /// Unlike [Navigation::GoTo], indices out of bounds aren't clamped, since they usually come from pages entered by the user.
+    pub fn jump(&mut self, index: usize) -> bool {
+        if !self.contains(index) {
+            return false;
+        }
+
+        self.index = index;
+        true
+    }
+
+    /// Applies the navigation to the position.
+    pub fn apply(&mut self, navigation: Navigation) {
+        self.index = match navigation {
+            Navigation::First => 0,
+            Navigation::Previous => return self.apply(Navigation::Step(-1)),
+            Navigation::Next => return self.apply(Navigation::Step(1)),
+            Navigation::Last => match self.length {
+                Some(length) => length - 1,
+                None => self.index,
+            },
+            Navigation::Step(step) => match self.length {
+                Some(length) if self.wraparound => {
+                    let length = length as isize;
+                    ((self.index as isize).wrapping_add(step % length)).rem_euclid(length) as usize
+                }
+                _ => self.clamp(self.index.saturating_add_signed(step)),
+            },
+            Navigation::GoTo(index) => self.clamp(index),
+        };
+    }
+
+    fn clamp(&self, index: usize) -> usize {
+        match self.length {
+            Some(length) => index.min(length - 1),
+            None => index,
+        }
+    }
+}
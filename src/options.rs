@@ -0,0 +1,191 @@
+use {
+    crate::{
+        dispatcher::InteractionDispatcher,
+        handle::PaginatorHandle,
+        http::RetryPolicy,
+        registry::SessionRegistry,
+        signer::Signer,
+        store::{SessionStore, StoreBinding},
+    },
+    serde::Serialize,
+    std::{sync::Arc, time::Duration},
+    tokio_util::sync::CancellationToken,
+};
+
+/// The default duration after which a slow render is deferred, leaving some headroom to Discord's 3-second response window.
+const DEFAULT_DEFER_AFTER: Duration = Duration::from_secs(2);
+
+/// The default number of button presses that can be queued while a page is rendered.
+const DEFAULT_QUEUE_CAPACITY: usize = 8;
+
+/// Options for a paginator session with the state `S`.
+///
+/// A [Duration] converts into options with that timeout, so passing just a timeout to the paginator works as well.
+pub struct PaginatorOptions<S> {
+    pub(crate) timeout: Duration,
+    pub(crate) defer_after: Duration,
+    pub(crate) loading_indicator: bool,
+    pub(crate) queue_capacity: usize,
+    pub(crate) cooldown: Option<Duration>,
+    pub(crate) truncate: bool,
+    pub(crate) refresh_button: bool,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) handle: Option<PaginatorHandle>,
+    pub(crate) dispatcher: Option<InteractionDispatcher>,
+    pub(crate) signer: Option<Signer>,
+    pub(crate) registry: Option<SessionRegistry>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    pub(crate) store: Option<StoreBinding<S>>,
+}
+
+impl<S> PaginatorOptions<S> {
+    /// Creates options with the duration after which the pagination will be cancelled if no interaction occurs.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            defer_after: DEFAULT_DEFER_AFTER,
+            loading_indicator: false,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            cooldown: None,
+            truncate: false,
+            refresh_button: false,
+            retry: RetryPolicy::default(),
+            handle: None,
+            dispatcher: None,
+            signer: None,
+            registry: None,
+            cancellation_token: None,
+            store: None,
+        }
+    }
+
+    /// Sets the duration after which the pagination will be cancelled if no interaction occurs.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the duration after which a button press is acknowledged if its page is still being rendered.
+    ///
+    /// Discord requires a response to a button press within 3 seconds. Slower pages are acknowledged with a deferred update
+    /// and the message is edited once the page is ready. Defaults to 2 seconds.
+    pub fn defer_after(mut self, duration: Duration) -> Self {
+        self.defer_after = duration;
+        self
+    }
+
+    /// Shows the [loading components](crate::View::loading_components) of the view while a deferred page is being rendered.
+    /// For the default view, all buttons are disabled and the page counter shows an hourglass.
+    pub fn loading_indicator(mut self, enabled: bool) -> Self {
+        self.loading_indicator = enabled;
+        self
+    }
+
+    /// Sets the number of button presses that can be queued while a page is rendered. Defaults to 8.
+    ///
+    /// Presses arriving while the queue is full are acknowledged and dropped. Consecutive navigation presses in the queue
+    /// are coalesced into a single movement, so spam-clicking only renders the page the user ends up on.
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Ignores button presses arriving within the given duration after the last accepted press.
+    /// Ignored presses are acknowledged, so they don't show up as failed interactions.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Cuts off texts of the embed exceeding Discord's limits with an ellipsis, instead of failing with an error naming the
+    /// text and the page. Fields beyond the 25th are dropped, and if the embed is still over 6000 characters in total, the
+    /// last fields are dropped and then the description is shortened.
+    ///
+    /// Components exceeding the limits always fail, since they come from the view.
+    pub fn truncate(mut self, enabled: bool) -> Self {
+        self.truncate = enabled;
+        self
+    }
+
+    /// Adds a "Refresh" button to the view of [paginate](crate::paginate), which re-runs the generator for the current page.
+    ///
+    /// This is the [DefaultViewWithRefresh](crate::DefaultViewWithRefresh). Custom views add their own buttons instead.
+    pub fn refresh_button(mut self, enabled: bool) -> Self {
+        self.refresh_button = enabled;
+        self
+    }
+
+    /// Sets how requests failing because of a rate limit or a transient Discord error are retried, instead of ending the session.
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sets a handle through which the session can be controlled from outside.
+    pub fn handle(mut self, handle: PaginatorHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Receives the button presses through the given dispatcher instead of a collector of the session.
+    pub fn dispatcher(mut self, dispatcher: InteractionDispatcher) -> Self {
+        self.dispatcher = Some(dispatcher);
+        self
+    }
+
+    /// Signs the component ids of the session, rejecting interactions with forged ids.
+    pub fn signer(mut self, signer: Signer) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Registers the session in the given registry while it is open.
+    pub fn registry(mut self, registry: SessionRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Ends the session with [CancellationType::Shutdown](crate::CancellationType::Shutdown) once the given token is cancelled.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+}
+
+impl<S: Serialize> PaginatorOptions<S> {
+    /// Saves the [record](crate::SessionRecord) of the session to the given store on every change.
+    pub fn store(mut self, store: Arc<dyn SessionStore>) -> Self {
+        self.store = Some(StoreBinding {
+            store,
+            serialize: |state| serde_json::to_value(state),
+        });
+        self
+    }
+}
+
+impl<S> Clone for PaginatorOptions<S> {
+    fn clone(&self) -> Self {
+        Self {
+            timeout: self.timeout,
+            defer_after: self.defer_after,
+            loading_indicator: self.loading_indicator,
+            queue_capacity: self.queue_capacity,
+            cooldown: self.cooldown,
+            truncate: self.truncate,
+            refresh_button: self.refresh_button,
+            retry: self.retry,
+            handle: self.handle.clone(),
+            dispatcher: self.dispatcher.clone(),
+            signer: self.signer.clone(),
+            registry: self.registry.clone(),
+            cancellation_token: self.cancellation_token.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<S> From<Duration> for PaginatorOptions<S> {
+    fn from(timeout: Duration) -> Self {
+        Self::new(timeout)
+    }
+}
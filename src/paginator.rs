@@ -1,377 +1,202 @@
 use {
-    crate::{Error, cancellation_type::CancellationType, event::Event},
-    poise::{
-        CreateReply,
-        serenity_prelude::{
-            self, ButtonStyle, ChannelId, Color, ComponentInteraction,
-            ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
-            CreateInteractionResponse, CreateInteractionResponseFollowup,
-            CreateInteractionResponseMessage, CreateQuickModal, ReactionType, UserId,
-            futures::StreamExt,
-        },
+    crate::{
+        Error,
+        cancellation_type::CancellationType,
+        custom_action::CustomAction,
+        dispatcher::InteractionDispatcher,
+        options::PaginatorOptions,
+        session::{self, Session},
+        store::SessionRecord,
+        transport::{Transport, discord::DiscordTransport, resumed::ResumedTransport},
+        view::default_view::{DefaultView, DefaultViewWithRefresh},
     },
-    std::{fmt::Display, sync::Arc, time::Duration},
-    tokio::sync::mpsc::UnboundedSender,
+    poise::serenity_prelude::{CreateEmbed, Http},
+    serde::de::DeserializeOwned,
+    std::sync::Arc,
 };
 
-fn get_paginate_components(
-    current_idx: usize,
-    length: usize,
-    ids: Arc<[String]>,
-    disable_all: bool,
-) -> Vec<CreateActionRow> {
-    let (left_disabled, right_disabled) = match (disable_all, current_idx, length) {
-        (true, ..) => (true, true),
-        (false, 0, _) => (true, false),
-        (false, idx, len) if idx == len - 1 => (false, true),
-        (false, ..) => (false, false),
-    };
-
-    vec![
-        CreateActionRow::Buttons(vec![
-            CreateButton::new(&ids[0])
-                .emoji(ReactionType::Unicode("⏪".to_owned()))
-                .style(ButtonStyle::Success)
-                .disabled(left_disabled),
-            CreateButton::new(&ids[1])
-                .emoji(ReactionType::Unicode("◀️".to_owned()))
-                .style(ButtonStyle::Secondary)
-                .disabled(left_disabled),
-            CreateButton::new(&ids[2])
-                .label(format!("{} / {}", current_idx + 1, length))
-                .disabled(true),
-            CreateButton::new(&ids[3])
-                .emoji(ReactionType::Unicode("▶️".to_owned()))
-                .style(ButtonStyle::Secondary)
-                .disabled(right_disabled),
-            CreateButton::new(&ids[4])
-                .emoji(ReactionType::Unicode("⏩".to_owned()))
-                .style(ButtonStyle::Success)
-                .disabled(right_disabled),
-        ]),
-        CreateActionRow::Buttons(vec![
-            CreateButton::new(&ids[5])
-                .style(ButtonStyle::Primary)
-                .label("Jump to page")
-                .disabled(disable_all),
-            CreateButton::new(&ids[6])
-                .style(ButtonStyle::Danger)
-                .label("Cancel")
-                .disabled(disable_all),
-        ]),
-    ]
-}
-
 /// A paginator function that allows users to navigate through a series of pages with a very fancy UI.
 ///
 /// Note on the generator function:
 ///
 /// It is called at the very beginning, when the paginator is created, and every time a button is pressed.
 /// It's also called when the pagination is cancelled through user input (the cancel button) or due to a timeout, represented by the [CancellationType](crate::cancellation_type::CancellationType).
+/// If the user navigates further while a page is rendered, the call is cancelled and the newer page is rendered instead.
 ///
 /// This function propagates the Context to the generator function, allowing you to access the context of the command.
-/// It also allows you to pass some state to the generator function, which can be used to store additional information across pages. Note that this state is cloned for each page.
+/// It also allows you to pass some state to the generator function, which can be used to store additional information across pages. The state is never cloned, the generator receives a [Session](crate::Session) handle through which it can be read and mutated, and changes persist across pages.
 ///
 /// Due to how buttons are handled, the index cannot go out of bounds (that is below 0 or above the length of the pages).
+/// This also holds when the length changes, the index is clamped to the last page in that case.
+///
+/// Returns the [CancellationType] the session ended with. If the message or its channel was deleted, or the bot lost access to it,
+/// the session ends with the matching variant instead of an error. Other errors end the session and are returned.
 ///
 /// # Arguments
 /// * `ctx` - The context of the command.
 /// * `generator` - A function that generates the embed for the current page.
-/// * `length` - The total number of pages.
-/// * `timeout` - The duration after which the pagination will be cancelled if no interaction occurs.
-/// * `state` - A state that can be used to store additional information across pages, accessed through the generator.
+/// * `length` - The initial number of pages, which can be changed during the session through [Session::set_length](crate::Session::set_length).
+/// * `options` - The [PaginatorOptions](crate::PaginatorOptions) of the session. Passing a [Duration](std::time::Duration) sets the duration after which the pagination will be cancelled if no interaction occurs.
+/// * `state` - A state that can be used to store additional information across pages, accessed through the [Session](crate::Session) handle.
 pub async fn paginate<'a, Gen, Fut, S, D, E>(
     ctx: poise::ApplicationContext<'a, D, E>,
     generator: Gen,
     length: usize,
-    timeout: Duration,
+    options: impl Into<PaginatorOptions<S>>,
     state: S,
-) -> Result<(), Error>
+) -> Result<CancellationType, Error>
 where
-    S: Clone + Send + Sync + 'static,
+    S: Send + 'static,
     Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
-    Gen: Fn(poise::ApplicationContext<'a, D, E>, usize, CancellationType, S) -> Fut,
+    Gen: Fn(poise::ApplicationContext<'a, D, E>, usize, CancellationType, Session<S>) -> Fut,
     D: Send + Sync + 'static + Sized,
     E: Send + Sync + 'static + Sized,
 {
-    let id = ctx.id();
+    let options = options.into();
+    let transport = DiscordTransport::new(ctx, options.dispatcher.clone());
 
-    let mut current_idx: usize = 0;
-
-    let ids: Arc<[String]> = [
-        format!("{}_fast_rewind", ctx.id()),
-        format!("{}_rewind", ctx.id()),
-        format!("{}_counter", ctx.id()),
-        format!("{}_forward", ctx.id()),
-        format!("{}_fast_forward", ctx.id()),
-        format!("{}_jump_to", ctx.id()),
-        format!("{}_cancel", ctx.id()),
-    ]
-    .into();
-
-    let components = get_paginate_components(current_idx, length, Arc::clone(&ids), false);
-
-    let first_embed = generator(
+    run::<_, _, D, _, _, _>(
         ctx,
-        current_idx,
-        CancellationType::NotCancelled,
-        state.clone(),
+        &transport,
+        ctx.id(),
+        generator,
+        length,
+        0,
+        options,
+        state,
     )
-    .await?;
-
-    let msg = ctx
-        .send(
-            CreateReply::default()
-                .embed(first_embed)
-                .components(components),
-        )
-        .await?;
-
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Event<Error>>();
-
-    tokio::spawn(handle_button_presses(
-        ctx.serenity_context().clone(),
-        tx,
-        id.to_string(),
-        ctx.author().id,
-        ctx.channel_id(),
-        timeout,
-        Arc::clone(&ids),
-    ));
-
-    while let Some(event) = rx.recv().await {
-        // This is a flag that is solely there for the "Jump to page" button.
-        // It is a flag to indicate whether to use a followup response or not,
-        // because the first one was used to create the modal.
-        let mut interaction_already_responded = false;
-
-        let interaction = match event {
-            Event::ToStart(interaction) => {
-                current_idx = 0;
-                interaction
-            }
-            Event::Previous(interaction) => {
-                current_idx = current_idx.saturating_sub(1);
-                interaction
-            }
-
-            Event::Next(interaction) => {
-                current_idx += 1;
-                interaction
-            }
-            Event::ToEnd(interaction) => {
-                current_idx = length - 1;
-                interaction
-            }
-
-            Event::Jump(interaction, page) => {
-                if page < length {
-                    current_idx = page;
-                    interaction_already_responded = true;
-                    interaction
-                } else {
-                    send_error_embed(
-                        ctx,
-                        interaction,
-                        format!("Page {} does not exist.", page + 1),
-                    )
-                    .await?;
-                    continue;
-                }
-            }
-
-            Event::CancelledByTimeout => {
-                msg.edit(
-                    ctx.into(),
-                    CreateReply::default()
-                        .embed(
-                            generator(ctx, current_idx, CancellationType::Timeout, state.clone())
-                                .await?,
-                        )
-                        .components(get_paginate_components(
-                            current_idx,
-                            length,
-                            Arc::clone(&ids),
-                            true,
-                        )),
-                )
-                .await?;
-                break;
-            }
-
-            Event::CancelledByUser(interaction) => {
-                let reply = CreateInteractionResponseMessage::default()
-                    .embed(
-                        generator(ctx, current_idx, CancellationType::UserInput, state.clone())
-                            .await?,
-                    )
-                    .components(get_paginate_components(
-                        current_idx,
-                        length,
-                        Arc::clone(&ids),
-                        true,
-                    ));
-
-                interaction
-                    .create_response(ctx, CreateInteractionResponse::UpdateMessage(reply))
-                    .await?;
-
-                break;
-            }
-
-            Event::Error(interaction, e) => {
-                send_error_embed(ctx, interaction, e).await?;
-                continue;
-            }
-        };
-
-        let embed = generator(
-            ctx,
-            current_idx,
-            CancellationType::NotCancelled,
-            state.clone(),
-        )
-        .await?;
-        let components = get_paginate_components(current_idx, length, Arc::clone(&ids), false);
-
-        match interaction_already_responded {
-            true => {
-                msg.edit(
-                    ctx.into(),
-                    CreateReply::default().embed(embed).components(components),
-                )
-                .await?;
-            }
-            false => {
-                let reply = CreateInteractionResponseMessage::default()
-                    .embed(embed)
-                    .components(components);
-
-                interaction
-                    .create_response(ctx, CreateInteractionResponse::UpdateMessage(reply))
-                    .await?;
-            }
-        };
-    }
-
-    Ok(())
+    .await
 }
 
-async fn handle_button_presses(
-    ctx: serenity_prelude::Context,
-    tx: UnboundedSender<Event<Error>>,
-    id: String,
-    author_id: UserId,
-    channel_id: ChannelId,
-    timeout: Duration,
-    ids: Arc<[String]>,
-) -> Result<(), Error> {
-    let mut collector = ComponentInteractionCollector::new(&ctx)
-        .author_id(author_id)
-        .channel_id(channel_id)
-        .timeout(timeout)
-        .filter(move |interaction| interaction.data.custom_id.starts_with(&id))
-        .stream();
-
-    while let Some(press) = collector.next().await {
-        handle_button_press(ctx.clone(), press, tx.clone(), Arc::clone(&ids)).await;
-    }
-
-    tx.send(Event::CancelledByTimeout).unwrap_or_default();
-    Ok(())
+/// Like [paginate], but running the session on the given [Transport] instead of a command, e.g. on a [MockTransport](crate::MockTransport) in tests.
+///
+/// The generator receives `cx` instead of the command context, which can be anything that is cheap to copy, like a reference to your data.
+pub async fn paginate_on<X, T, Gen, Fut, S>(
+    cx: X,
+    transport: &T,
+    generator: Gen,
+    length: usize,
+    options: impl Into<PaginatorOptions<S>>,
+    state: S,
+) -> Result<CancellationType, Error>
+where
+    X: Copy + Send,
+    T: Transport,
+    S: Send + 'static,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+{
+    let id = transport.session_id();
+
+    run::<_, _, (), _, _, _>(
+        cx,
+        transport,
+        id,
+        generator,
+        length,
+        0,
+        options.into(),
+        state,
+    )
+    .await
 }
 
-async fn handle_button_press(
-    ctx: serenity_prelude::Context,
-    press: ComponentInteraction,
-    tx: UnboundedSender<Event<Error>>,
-    ids: Arc<[String]>,
-) {
-    match press.data.custom_id.as_str() {
-        id if id == ids[0] => {
-            // Fast rewind
-            tx.send(Event::ToStart(press)).unwrap_or_default();
-        }
-        id if id == ids[1] => {
-            // Rewind
-            tx.send(Event::Previous(press)).unwrap_or_default();
-        }
-        id if id == ids[3] => {
-            // Forward
-            tx.send(Event::Next(press)).unwrap_or_default();
-        }
-        id if id == ids[4] => {
-            // Fast forward
-            tx.send(Event::ToEnd(press)).unwrap_or_default();
-        }
-        id if id == ids[5] => {
-            // Jump to page
-
-            let modal = CreateQuickModal::new("Jump to Page")
-                .timeout(Duration::from_secs(30))
-                .short_field("Page Number");
-
-            let tx = tx.clone();
-
-            tokio::spawn(async move {
-                let response = press.quick_modal(&ctx, modal).await;
-
-                let event = match response {
-                    Ok(Some(response)) => {
-                        let number = response.inputs[0].parse::<usize>();
-
-                        let event = match number {
-                            Ok(num) => Event::Jump(press, num - 1),
-                            Err(e) => Event::Error(press, Error::from(e)),
-                        };
-
-                        response
-                            .interaction
-                            .create_response(ctx, CreateInteractionResponse::Acknowledge)
-                            .await
-                            .ok();
-
-                        event
-                    }
-                    Ok(None) => return,
-                    Err(e) => Event::Error(press, e.into()),
-                };
+/// Resumes a session of [paginate] from its [SessionRecord], e.g. one listed from a [SessionStore](crate::SessionStore) after a restart.
+///
+/// The page at the recorded index is edited into the existing message, with the state deserialized from the record.
+/// The button presses are received through the dispatcher, which has to be fed the interactions the bot receives.
+/// The generator receives `cx` instead of the command context, like with [paginate_on].
+///
+/// Pass the same [Signer](crate::Signer) as the original session, so the buttons of the message keep working.
+/// Ephemeral messages can't be resumed, since they can only be edited through the token of the command interaction.
+pub async fn resume<X, Gen, Fut, S>(
+    cx: X,
+    http: Arc<Http>,
+    dispatcher: InteractionDispatcher,
+    record: SessionRecord,
+    generator: Gen,
+    options: impl Into<PaginatorOptions<S>>,
+) -> Result<CancellationType, Error>
+where
+    X: Copy + Send,
+    S: DeserializeOwned + Send + 'static,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+{
+    let state = record.state()?;
+    let (id, length, index) = (record.session_id, record.length, record.index);
+    let transport = ResumedTransport::new(http, record, dispatcher);
+
+    run::<_, _, (), _, _, _>(
+        cx,
+        &transport,
+        id,
+        generator,
+        length,
+        index,
+        options.into(),
+        state,
+    )
+    .await
+}
 
-                tx.send(event).unwrap_or_default();
-            });
+/// Runs the session with the [DefaultView] from the given index, with a "Refresh" button if the options ask for one.
+#[allow(clippy::too_many_arguments)]
+async fn run<X, T, D, Gen, Fut, S>(
+    cx: X,
+    transport: &T,
+    id: u64,
+    generator: Gen,
+    length: usize,
+    index: usize,
+    options: PaginatorOptions<S>,
+    state: S,
+) -> Result<CancellationType, Error>
+where
+    X: Copy + Send,
+    T: Transport,
+    D: 'static,
+    S: Send + 'static,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+{
+    match options.refresh_button {
+        true => {
+            session::run::<_, _, D, DefaultViewWithRefresh, Error, (), _, _, _, _, _>(
+                cx,
+                transport,
+                DefaultViewWithRefresh::ids(id),
+                generator,
+                ignore_custom_event,
+                length,
+                index,
+                options,
+                state,
+            )
+            .await
         }
-
-        id if id == ids[6] => {
-            // Cancel
-            tx.send(Event::CancelledByUser(press)).unwrap_or_default();
+        false => {
+            session::run::<_, _, D, DefaultView, Error, (), _, _, _, _, _>(
+                cx,
+                transport,
+                <DefaultView>::ids(id),
+                generator,
+                ignore_custom_event,
+                length,
+                index,
+                options,
+                state,
+            )
+            .await
         }
-
-        _ => unreachable!("Unexpected button ID: {}", press.data.custom_id),
     }
 }
 
-async fn send_error_embed<D, E>(
-    ctx: poise::ApplicationContext<'_, D, E>,
-    interaction: ComponentInteraction,
-    description: impl Display,
-) -> Result<(), Error>
-where
-    D: Send + Sync + 'static,
-    E: Send + Sync + 'static,
-{
-    let embed = CreateEmbed::new()
-        .title("Error")
-        .description(description.to_string())
-        .color(Color::RED);
-
-    interaction
-        .create_followup(
-            ctx,
-            CreateInteractionResponseFollowup::default()
-                .embed(embed)
-                .ephemeral(true),
-        )
-        .await?;
-
-    Ok(())
+async fn ignore_custom_event<X, S>(
+    _cx: X,
+    _event: (),
+    _current_idx: usize,
+    _session: Session<S>,
+) -> Result<CustomAction, Error> {
+    Ok(CustomAction::None)
 }
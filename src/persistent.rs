@@ -0,0 +1,432 @@
+use {
+    crate::{
+        Error,
+        cancellation_type::CancellationType,
+        event::Event,
+        navigator::{Navigation, Navigator},
+        session::send_error_embed,
+        signer::Signer,
+        transport::Interactions,
+        view::{View, default_view::DefaultView},
+    },
+    poise::{
+        ApplicationContext, CreateReply,
+        serenity_prelude::{
+            self, ComponentInteraction, CreateEmbed, CreateInteractionResponse,
+            CreateInteractionResponseMessage, EditMessage, FullEvent, Interaction, UserId,
+            futures::future::BoxFuture,
+        },
+    },
+    std::{collections::HashMap, fmt::Display, str::FromStr, sync::Arc},
+};
+
+/// The prefix of all component ids of persistent paginators.
+const PREFIX: &str = "pp";
+
+/// The maximum length of a source key, which keeps the component ids below Discord's limit of 100 characters.
+const MAX_KEY_LENGTH: usize = 24;
+
+type Generator<X> = dyn Fn(X, usize, CancellationType) -> BoxFuture<'static, Result<PersistentPage, Error>>
+    + Send
+    + Sync;
+
+/// A page rendered by the generator of a persistent paginator.
+pub struct PersistentPage {
+    pub embed: CreateEmbed,
+
+    /// The total number of pages. Since persistent paginators have no session, it is reported on every render.
+    pub length: usize,
+}
+
+impl PersistentPage {
+    pub fn new(embed: CreateEmbed, length: usize) -> Self {
+        Self { embed, length }
+    }
+}
+
+/// The generators of persistent paginators, looked up by their source key.
+///
+/// Persistent paginators have no running session. The page index, the page count, the owner and the source key are encoded
+/// into the component ids instead, and every button press is handled by [PersistentPaginators::handle_event], which rebuilds
+/// the page from the registered generator. Because of this, their buttons keep working after the bot restarts.
+///
+/// Register all generators at startup, see the [crate-level setup](crate#setup) for where to keep them.
+///
+/// Persistent paginators never time out and, since there is no session, have no state apart from what the generator loads itself.
+/// The message is edited through the channel after a "Jump to page" modal, so the bot needs access to the channel and the message can't be ephemeral.
+///
+/// The generators receive the serenity context `X`. Other contexts can be used with [PersistentPaginators::handle_press],
+/// e.g. to test the generators on a [MockTransport](crate::MockTransport).
+pub struct PersistentPaginators<X = serenity_prelude::Context> {
+    generators: HashMap<String, Arc<Generator<X>>>,
+    signer: Option<Signer>,
+}
+
+impl<X> PersistentPaginators<X> {
+    pub fn new() -> Self {
+        Self {
+            generators: HashMap::new(),
+            signer: None,
+        }
+    }
+
+    /// Signs the component ids of persistent paginators, rejecting interactions with forged ids.
+    ///
+    /// Since the page index and the owner are encoded into the ids, this is strongly recommended.
+    pub fn signer(mut self, signer: Signer) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Registers a generator under the given source key.
+    ///
+    /// The generator receives the serenity context instead of the command context, since the command may be long gone when a button is pressed.
+    ///
+    /// # Panics
+    /// Panics if the key is longer than 24 characters or contains a `:`.
+    pub fn register<Gen, Fut>(mut self, key: impl Into<String>, generator: Gen) -> Self
+    where
+        Gen: Fn(X, usize, CancellationType) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<PersistentPage, Error>> + Send + 'static,
+    {
+        let key = key.into();
+
+        assert!(
+            key.len() <= MAX_KEY_LENGTH && !key.contains(':'),
+            "Invalid persistent paginator key: {key}"
+        );
+
+        self.generators.insert(
+            key,
+            Arc::new(move |ctx, idx, cancellation_type| {
+                Box::pin(generator(ctx, idx, cancellation_type))
+            }),
+        );
+        self
+    }
+
+    /// Handles a button press on a persistent paginator, responding through `interactions`.
+    ///
+    /// This is what [PersistentPaginators::handle_event] does for every component interaction, with the generators receiving
+    /// `cx` instead of the serenity context. Presses that don't belong to a registered persistent paginator are ignored.
+    pub async fn handle_press(
+        &self,
+        cx: X,
+        interactions: Arc<dyn Interactions>,
+        press: &ComponentInteraction,
+    ) -> Result<(), Error>
+    where
+        X: Clone,
+    {
+        let Some(id) = PersistentId::parse(&press.data.custom_id) else {
+            return Ok(());
+        };
+
+        if let Some(signer) = &self.signer
+            && !signer.verify(&press.data.custom_id)
+        {
+            tracing::warn!(
+                custom_id = press.data.custom_id,
+                user_id = %press.user.id,
+                "Rejected a persistent paginator interaction with an invalid signature"
+            );
+            return Ok(());
+        }
+
+        let Some(generator) = self.generators.get(&id.key) else {
+            return Ok(());
+        };
+
+        // Only the buttons of the view are handled, not ids with the right prefix and an unknown button.
+        if !self.ids(&id).contains(&press.data.custom_id) {
+            return Ok(());
+        }
+
+        if press.user.id != id.owner {
+            interactions
+                .respond(
+                    press,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("You cannot control this paginator.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+
+            return Ok(());
+        }
+
+        self.handle_button_press(cx, interactions, press.clone(), generator.as_ref(), id)
+            .await
+    }
+
+    async fn handle_button_press(
+        &self,
+        cx: X,
+        interactions: Arc<dyn Interactions>,
+        press: ComponentInteraction,
+        generator: &Generator<X>,
+        mut id: PersistentId,
+    ) -> Result<(), Error>
+    where
+        X: Clone,
+    {
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Event<Error>>(1);
+
+        <DefaultView as View<(), Error>>::on_button_press(
+            Arc::clone(&interactions),
+            press.clone(),
+            tx,
+            self.ids(&id),
+        )
+        .await?;
+
+        // The sender is dropped without an event if the "Jump to page" modal is dismissed.
+        let Some(event) = rx.recv().await else {
+            return Ok(());
+        };
+
+        let interactions = interactions.as_ref();
+        let mut cancellation_type = CancellationType::NotCancelled;
+
+        let mut navigator = Navigator::new(id.length).with_index(id.index);
+
+        let interaction = match event {
+            Event::ToStart(interaction) => {
+                navigator.apply(Navigation::First);
+                Some(interaction)
+            }
+            Event::Previous(interaction) => {
+                navigator.apply(Navigation::Previous);
+                Some(interaction)
+            }
+            Event::Next(interaction) => {
+                navigator.apply(Navigation::Next);
+                Some(interaction)
+            }
+            Event::ToEnd(interaction) => {
+                navigator.apply(Navigation::Last);
+                Some(interaction)
+            }
+            Event::Jump(interaction, page) => {
+                if navigator.jump(page) {
+                    // The interaction was already used to respond with the modal.
+                    None
+                } else {
+                    return send_error_embed(
+                        interactions,
+                        &interaction,
+                        format!("Page {} does not exist.", page.saturating_add(1)),
+                    )
+                    .await;
+                }
+            }
+            Event::Refresh(interaction) | Event::Custom(interaction, ()) => Some(interaction),
+            Event::CancelledByUser(interaction) => {
+                cancellation_type = CancellationType::UserInput;
+                Some(interaction)
+            }
+            Event::Error(interaction, e) => {
+                return send_error_embed(interactions, &interaction, e).await;
+            }
+            // Only sent by the collector of a running session.
+            Event::CancelledByTimeout => return Ok(()),
+        };
+
+        id.index = navigator.index();
+
+        let (embed, components) = self.render(cx, generator, id, cancellation_type).await?;
+
+        match interaction {
+            Some(interaction) => {
+                let reply = CreateInteractionResponseMessage::default()
+                    .embed(embed)
+                    .components(components);
+
+                interactions
+                    .respond(
+                        &interaction,
+                        CreateInteractionResponse::UpdateMessage(reply),
+                    )
+                    .await?;
+            }
+            None => {
+                interactions
+                    .edit_message(
+                        &press,
+                        EditMessage::new().embed(embed).components(components),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the page of a persistent paginator, clamping the index to the length reported by the generator.
+    async fn render(
+        &self,
+        cx: X,
+        generator: &Generator<X>,
+        mut id: PersistentId,
+        cancellation_type: CancellationType,
+    ) -> Result<(CreateEmbed, Vec<serenity_prelude::CreateActionRow>), Error>
+    where
+        X: Clone,
+    {
+        let embed = loop {
+            let page = generator(cx.clone(), id.index, cancellation_type).await?;
+
+            id.length = page.length.max(1);
+
+            if id.index < id.length {
+                break page.embed;
+            }
+
+            id.index = id.length - 1;
+        };
+
+        let components = <DefaultView as View<(), Error>>::rerender_components(
+            self.ids(&id),
+            id.index,
+            id.length,
+            cancellation_type != CancellationType::NotCancelled,
+        );
+
+        Ok((embed, components))
+    }
+
+    /// Creates the component ids for the given position, signed if a signer is set.
+    fn ids(&self, id: &PersistentId) -> Arc<[String]> {
+        let ids = <DefaultView>::ids(id);
+
+        match &self.signer {
+            Some(signer) => ids.iter().map(|id| signer.sign(id)).collect(),
+            None => ids,
+        }
+    }
+}
+
+impl PersistentPaginators {
+    /// Sends the first page of the persistent paginator registered under the given key.
+    pub async fn paginate<D, E>(
+        &self,
+        ctx: ApplicationContext<'_, D, E>,
+        key: &str,
+    ) -> Result<(), Error>
+    where
+        D: Send + Sync + 'static,
+        E: Send + Sync + 'static,
+    {
+        let generator = self
+            .generators
+            .get(key)
+            .ok_or_else(|| format!("No persistent paginator registered for key {key}"))?;
+
+        let (embed, components) = self
+            .render(
+                ctx.serenity_context().clone(),
+                generator.as_ref(),
+                PersistentId {
+                    key: key.to_owned(),
+                    owner: ctx.author().id,
+                    index: 0,
+                    length: 1,
+                },
+                CancellationType::NotCancelled,
+            )
+            .await?;
+
+        ctx.send(CreateReply::default().embed(embed).components(components))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handles button presses on persistent paginators. Call this from the poise `event_handler` for every event.
+    ///
+    /// Events that don't belong to a registered persistent paginator are ignored.
+    pub async fn handle_event(
+        &self,
+        ctx: &serenity_prelude::Context,
+        event: &FullEvent,
+    ) -> Result<(), Error> {
+        let FullEvent::InteractionCreate {
+            interaction: Interaction::Component(press),
+        } = event
+        else {
+            return Ok(());
+        };
+
+        self.handle_press(ctx.clone(), Arc::new(ctx.clone()), press)
+            .await
+    }
+}
+
+impl<X> Default for PersistentPaginators<X> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<X> Clone for PersistentPaginators<X> {
+    fn clone(&self) -> Self {
+        Self {
+            generators: self.generators.clone(),
+            signer: self.signer.clone(),
+        }
+    }
+}
+
+/// The position of a persistent paginator, which is encoded into its component ids.
+///
+/// The ids have the form `pp:{key}:{owner}:{index}:{length}_{button}`, followed by the signature if a [Signer] is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistentId {
+    /// The key the generator is registered under.
+    pub key: String,
+
+    /// The user who is allowed to press the buttons.
+    pub owner: UserId,
+
+    pub index: usize,
+
+    /// The number of pages as the generator last reported it.
+    pub length: usize,
+}
+
+impl PersistentId {
+    /// Parses the position from a component id, ignoring the button and the signature.
+    ///
+    /// Returns `None` if the id doesn't belong to a persistent paginator.
+    pub fn parse(custom_id: &str) -> Option<Self> {
+        let mut parts = custom_id
+            .strip_prefix(PREFIX)?
+            .strip_prefix(':')?
+            .split(':');
+
+        let key = parts.next()?.to_owned();
+        let owner = UserId::from_str(parts.next()?).ok()?;
+        let index = parts.next()?.parse().ok()?;
+        let (length, _button) = parts.next()?.split_once('_')?;
+
+        Some(Self {
+            key,
+            owner,
+            index,
+            length: length.parse().ok()?,
+        })
+    }
+}
+
+/// Formats the prefix of the component ids, which [DefaultView::ids] appends the buttons to.
+impl Display for PersistentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{PREFIX}:{}:{}:{}:{}",
+            self.key, self.owner, self.index, self.length
+        )
+    }
+}
@@ -0,0 +1,305 @@
+use {
+    crate::transport::mock::MockMessage,
+    serde_json::Value,
+    std::fmt::{self, Display},
+};
+
+#[cfg(feature = "preview")]
+pub use interactive::{custom_preview, preview};
+
+/// Renders the message as text: the embed with its title, description, fields and footer, then a line per action row.
+///
+/// Enabled buttons are shown as `[label]`, disabled ones as `(label)`.
+impl Display for MockMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let embed = &self.embed;
+        let mut sections = Vec::new();
+
+        let header: Vec<&str> = [&embed["author"]["name"], &embed["title"]]
+            .into_iter()
+            .filter_map(Value::as_str)
+            .chain(
+                embed["description"]
+                    .as_str()
+                    .into_iter()
+                    .flat_map(str::lines),
+            )
+            .collect();
+        sections.push(header);
+
+        for field in embed["fields"].as_array().into_iter().flatten() {
+            let name = field["name"].as_str().unwrap_or_default();
+            let value = field["value"].as_str().unwrap_or_default();
+
+            sections.push([name].into_iter().chain(value.lines()).collect());
+        }
+
+        sections.push(embed["footer"]["text"].as_str().into_iter().collect());
+        sections.retain(|section| !section.is_empty());
+
+        for (i, section) in sections.iter().enumerate() {
+            if i > 0 {
+                writeln!(f, "│")?;
+            }
+
+            for line in section {
+                writeln!(f, "│ {line}")?;
+            }
+        }
+
+        for row in rows(&self.components) {
+            let components: Vec<String> = row
+                .iter()
+                .map(|component| match component["disabled"] == true {
+                    true => format!("({})", label(component)),
+                    false => format!("[{}]", label(component)),
+                })
+                .collect();
+
+            writeln!(f, "{}", components.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn rows(components: &Value) -> impl Iterator<Item = &Vec<Value>> {
+    components
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|row| row["components"].as_array())
+}
+
+/// The emoji and label of a button, or the placeholder of a select menu.
+fn label(component: &Value) -> String {
+    let parts: Vec<&str> = [
+        &component["emoji"]["name"],
+        &component["label"],
+        &component["placeholder"],
+    ]
+    .into_iter()
+    .filter_map(Value::as_str)
+    .collect();
+
+    match parts.is_empty() {
+        true => component["custom_id"].as_str().unwrap_or("?").to_owned(),
+        false => parts.join(" "),
+    }
+}
+
+#[cfg(feature = "preview")]
+mod interactive {
+    use {
+        super::{label, rows},
+        crate::{
+            Error,
+            cancellation_type::CancellationType,
+            custom_action::CustomAction,
+            custom_paginator::{self, PaginationInfo},
+            options::PaginatorOptions,
+            paginator,
+            session::Session,
+            transport::mock::{MockRequest, MockTransport},
+        },
+        poise::serenity_prelude::CreateEmbed,
+        std::{pin::pin, sync::Arc},
+        tokio::sync::mpsc::{self, UnboundedReceiver},
+    };
+
+    /// Runs a paginator with the [DefaultView](crate::DefaultView) in the terminal, for developing page generators
+    /// without Discord.
+    ///
+    /// Every page is printed as text, followed by a key for every enabled button. Typing a key and pressing Enter presses
+    /// the button, `q` quits. Modals are answered by typing their input, or dismissed with an empty line.
+    ///
+    /// The generator receives `cx` instead of the command context, like with [paginate_on](crate::paginate_on).
+    pub async fn preview<X, Gen, Fut, S>(
+        cx: X,
+        generator: Gen,
+        length: usize,
+        options: impl Into<PaginatorOptions<S>>,
+        state: S,
+    ) -> Result<(), Error>
+    where
+        X: Copy + Send,
+        S: Send + 'static,
+        Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+        Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    {
+        let transport = MockTransport::new();
+        let session = paginator::paginate_on(cx, &transport, generator, length, options, state);
+
+        run(&transport, session).await
+    }
+
+    /// Runs a paginator with a custom view in the terminal, like [preview].
+    ///
+    /// The component ids are passed in, as [View::create_ids](crate::View::create_ids) would create them for a
+    /// session with the id 1.
+    pub async fn custom_preview<P, X, Fut, HandlerFut, S>(
+        cx: X,
+        ids: Arc<[String]>,
+        generator: impl Fn(X, usize, CancellationType, Session<S>) -> Fut,
+        handler: impl Fn(X, P::CustomEvent, usize, Session<S>) -> HandlerFut,
+        length: usize,
+        options: impl Into<PaginatorOptions<S>>,
+        state: S,
+    ) -> Result<(), Error>
+    where
+        P: PaginationInfo,
+        X: Copy,
+        S: Send + 'static,
+        Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+        HandlerFut: Future<Output = Result<CustomAction, Error>> + Send,
+    {
+        let transport = MockTransport::new();
+        let session = custom_paginator::paginate_on::<P, _, _, _, _, _>(
+            cx, &transport, ids, generator, handler, length, options, state,
+        );
+
+        run(&transport, session).await
+    }
+
+    async fn run(
+        transport: &MockTransport,
+        session: impl Future<Output = Result<CancellationType, Error>>,
+    ) -> Result<(), Error> {
+        let mut session = pin!(session);
+        let mut lines = stdin_lines();
+        let mut modal_open = false;
+
+        loop {
+            tokio::select! {
+                cancellation_type = &mut session => {
+                    while let Some(request) = transport.try_next_request() {
+                        show(transport, &request);
+                    }
+
+                    println!("The paginator was cancelled ({:?})", cancellation_type?);
+                    return Ok(());
+                }
+                request = transport.next_request() => {
+                    modal_open |= matches!(request, MockRequest::Modal { .. });
+                    show(transport, &request);
+                }
+                line = lines.recv() => {
+                    let Some(line) = line else {
+                        // Stdin was closed.
+                        return Ok(());
+                    };
+
+                    let line = line.trim();
+
+                    if modal_open {
+                        modal_open = false;
+
+                        match line.is_empty() {
+                            true => transport.dismiss_modal(),
+                            false => transport.submit_modal([line]),
+                        }
+                    } else if line == "q" {
+                        return Ok(());
+                    } else {
+                        press(transport, line);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Presses the button with the given key, as shown below the message.
+    fn press(transport: &MockTransport, key: &str) {
+        let buttons = buttons(transport);
+
+        let button = key
+            .parse::<usize>()
+            .ok()
+            .and_then(|key| buttons.get(key.checked_sub(1)?));
+
+        match button {
+            Some((custom_id, _, true)) => {
+                if let Err(e) = transport.press(custom_id) {
+                    println!("{e}");
+                }
+            }
+            Some((_, label, false)) => println!("{label} is disabled"),
+            None => println!("There is no button with the key {key:?}"),
+        }
+    }
+
+    fn show(transport: &MockTransport, request: &MockRequest) {
+        match request {
+            MockRequest::Modal { .. } => {
+                println!(
+                    "A modal is open, type its input and press Enter, or press Enter to dismiss it"
+                );
+            }
+            MockRequest::Followup { followup, .. } => {
+                for embed in followup["embeds"].as_array().into_iter().flatten() {
+                    println!("> {}", embed["description"].as_str().unwrap_or_default());
+                }
+            }
+            request if request.is_acknowledgement() => {}
+            _ => {
+                let Some(message) = transport.message() else {
+                    return;
+                };
+
+                // Clears the terminal, so only the current page is shown.
+                print!("\x1b[2J\x1b[H");
+                print!("{message}");
+
+                let keys: Vec<String> = buttons(transport)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, (_, _, enabled))| *enabled)
+                    .map(|(i, (_, label, _))| format!("{} {label}", i + 1))
+                    .collect();
+
+                if !keys.is_empty() {
+                    println!();
+                    println!("{} | q quit", keys.join(" | "));
+                }
+            }
+        }
+    }
+
+    /// The id, label and whether it's enabled of every component of the current message, numbered by their keys.
+    fn buttons(transport: &MockTransport) -> Vec<(String, String, bool)> {
+        let Some(message) = transport.message() else {
+            return Vec::new();
+        };
+
+        rows(&message.components)
+            .flatten()
+            .filter_map(|component| {
+                let custom_id = component["custom_id"].as_str()?;
+                Some((
+                    custom_id.to_owned(),
+                    label(component),
+                    component["disabled"] != true,
+                ))
+            })
+            .collect()
+    }
+
+    /// The lines typed into the terminal, read on a separate thread since reading stdin blocks.
+    fn stdin_lines() -> UnboundedReceiver<String> {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        rx
+    }
+}
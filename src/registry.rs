@@ -0,0 +1,177 @@
+use {
+    crate::{cancellation_type::CancellationType, handle::PaginatorHandle},
+    poise::serenity_prelude::{ChannelId, UserId},
+    std::{
+        collections::HashMap,
+        sync::{
+            Arc, Mutex, MutexGuard,
+            atomic::{AtomicBool, AtomicU64, Ordering},
+        },
+    },
+    tokio::sync::Notify,
+};
+
+/// A registry of the active paginator sessions.
+///
+/// Sessions are registered when it is passed through [PaginatorOptions::registry](crate::PaginatorOptions::registry),
+/// see the [crate-level setup](crate#setup) for where to keep it.
+///
+/// The registry can limit the number of concurrent paginators per user or channel,
+/// and finalize every open paginator through [SessionRegistry::shutdown] before the bot exits.
+#[derive(Clone, Default)]
+pub struct SessionRegistry {
+    shared: Arc<Shared>,
+    per_user_limit: Option<usize>,
+    per_channel_limit: Option<usize>,
+}
+
+#[derive(Default)]
+struct Shared {
+    sessions: Mutex<HashMap<u64, Entry>>,
+    next_sequence: AtomicU64,
+    shutting_down: AtomicBool,
+    ended: Notify,
+}
+
+struct Entry {
+    owner: UserId,
+    channel_id: ChannelId,
+    handle: PaginatorHandle,
+    /// The order in which the sessions were opened, used to close the oldest ones first.
+    sequence: u64,
+    /// Whether the session was asked to close, in which case it doesn't count towards the limits anymore.
+    closing: bool,
+}
+
+impl SessionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of concurrent paginators per user. Opening a new one closes the oldest one with [CancellationType::Replaced].
+    pub fn per_user_limit(mut self, limit: usize) -> Self {
+        self.per_user_limit = Some(limit.max(1));
+        self
+    }
+
+    /// Limits the number of concurrent paginators per channel. Opening a new one closes the oldest one with [CancellationType::Replaced].
+    pub fn per_channel_limit(mut self, limit: usize) -> Self {
+        self.per_channel_limit = Some(limit.max(1));
+        self
+    }
+
+    /// The number of open sessions.
+    pub fn count(&self) -> usize {
+        self.sessions().len()
+    }
+
+    /// Closes every open session with [CancellationType::Shutdown] and waits until all of them are finalized.
+    ///
+    /// Sessions opened afterwards are closed right away. Wrap this in a timeout if a slow generator shouldn't delay the exit.
+    pub async fn shutdown(&self) {
+        self.shared.shutting_down.store(true, Ordering::Release);
+
+        for entry in self.sessions().values_mut() {
+            entry.closing = true;
+            entry.handle.close_with(CancellationType::Shutdown);
+        }
+
+        loop {
+            let ended = self.shared.ended.notified();
+            tokio::pin!(ended);
+            ended.as_mut().enable();
+
+            if self.count() == 0 {
+                break;
+            }
+
+            ended.await;
+        }
+    }
+
+    /// Registers a session, closing the oldest sessions of the same user or channel if a limit is exceeded.
+    /// The session is unregistered once the returned [Registration] is dropped.
+    pub(crate) fn register(
+        &self,
+        session_id: u64,
+        owner: UserId,
+        channel_id: ChannelId,
+        handle: PaginatorHandle,
+    ) -> Registration {
+        let mut sessions = self.sessions();
+
+        let shutting_down = self.shared.shutting_down.load(Ordering::Acquire);
+
+        if shutting_down {
+            handle.close_with(CancellationType::Shutdown);
+        }
+
+        if let Some(limit) = self.per_user_limit {
+            close_oldest(&mut sessions, limit, |entry| entry.owner == owner);
+        }
+
+        if let Some(limit) = self.per_channel_limit {
+            close_oldest(&mut sessions, limit, |entry| entry.channel_id == channel_id);
+        }
+
+        sessions.insert(
+            session_id,
+            Entry {
+                owner,
+                channel_id,
+                handle,
+                sequence: self.shared.next_sequence.fetch_add(1, Ordering::Relaxed),
+                closing: shutting_down,
+            },
+        );
+
+        Registration {
+            shared: Arc::clone(&self.shared),
+            session_id,
+        }
+    }
+
+    fn sessions(&self) -> MutexGuard<'_, HashMap<u64, Entry>> {
+        lock(&self.shared.sessions)
+    }
+}
+
+/// Closes the oldest sessions matching the filter, so that a new one can be opened without exceeding the limit.
+fn close_oldest(sessions: &mut HashMap<u64, Entry>, limit: usize, filter: impl Fn(&Entry) -> bool) {
+    let mut open = sessions
+        .values_mut()
+        .filter(|entry| !entry.closing && filter(entry))
+        .collect::<Vec<_>>();
+
+    if open.len() < limit {
+        return;
+    }
+
+    open.sort_by_key(|entry| entry.sequence);
+
+    let excess = open.len() + 1 - limit;
+
+    for entry in open.into_iter().take(excess) {
+        entry.closing = true;
+        entry.handle.close_with(CancellationType::Replaced);
+    }
+}
+
+fn lock(sessions: &Mutex<HashMap<u64, Entry>>) -> MutexGuard<'_, HashMap<u64, Entry>> {
+    sessions
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Unregisters a session from its [SessionRegistry] when dropped.
+pub(crate) struct Registration {
+    shared: Arc<Shared>,
+    session_id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        lock(&self.shared.sessions).remove(&self.session_id);
+        self.shared.ended.notify_waiters();
+    }
+}
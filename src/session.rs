@@ -0,0 +1,979 @@
+use {
+    crate::{
+        Error,
+        cancellation_type::CancellationType,
+        custom_action::CustomAction,
+        event::Event,
+        handle::{Command, NavigationEvent},
+        http::{RetryPolicy, is_expired_interaction},
+        limits,
+        navigator::{Navigation, Navigator},
+        options::PaginatorOptions,
+        store::SessionRecord,
+        transport::{Interactions, Transport},
+        view::View,
+    },
+    poise::serenity_prelude::{
+        Color, ComponentInteraction, CreateActionRow, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseFollowup, CreateInteractionResponseMessage, CreateQuickModal,
+        EditInteractionResponse, EditMessage, InteractionId,
+        futures::{StreamExt, future::BoxFuture, stream::BoxStream},
+    },
+    std::{
+        collections::HashMap,
+        fmt::Display,
+        hash::{DefaultHasher, Hash, Hasher},
+        sync::{
+            Arc,
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+        },
+        time::Duration,
+    },
+    tokio::{
+        sync::{
+            Mutex, MutexGuard, Notify,
+            mpsc::{Receiver, Sender, UnboundedReceiver},
+            oneshot,
+        },
+        time::Instant,
+    },
+    tokio_util::sync::CancellationToken,
+};
+
+/// A handle to the state of a running paginator session.
+///
+/// The state is shared between the generator and the custom event handler, so changes made to it persist across pages.
+/// Cloning the handle is cheap, the state itself is never cloned.
+pub struct Session<S> {
+    shared: Arc<Shared<S>>,
+}
+
+struct Shared<S> {
+    state: Mutex<S>,
+    length: AtomicUsize,
+    refresh_requested: AtomicBool,
+    changed: Notify,
+}
+
+impl<S> Session<S> {
+    pub(crate) fn new(state: S, length: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(state),
+                length: AtomicUsize::new(length.max(1)),
+                refresh_requested: AtomicBool::new(false),
+                changed: Notify::new(),
+            }),
+        }
+    }
+
+    /// Locks the state of the session, giving mutable access to it until the guard is dropped.
+    pub async fn state(&self) -> MutexGuard<'_, S> {
+        self.shared.state.lock().await
+    }
+
+    /// The current number of pages.
+    pub fn length(&self) -> usize {
+        self.shared.length.load(Ordering::Acquire)
+    }
+
+    /// Changes the number of pages. A session always has at least one page.
+    ///
+    /// If the current index is out of bounds afterwards, it is clamped to the last page.
+    /// When called from the generator, the change is picked up by the render in progress,
+    /// otherwise the message is re-rendered to update the page counter and the disabled buttons.
+    pub fn set_length(&self, length: usize) {
+        self.shared.length.store(length.max(1), Ordering::Release);
+        self.shared.changed.notify_one();
+    }
+
+    /// Re-runs the generator for the current page and updates the message.
+    ///
+    /// Useful for pages showing live data, which would otherwise only be updated when navigating.
+    pub fn refresh(&self) {
+        self.shared.refresh_requested.store(true, Ordering::Release);
+        self.shared.changed.notify_one();
+    }
+
+    async fn changed(&self) {
+        self.shared.changed.notified().await;
+    }
+
+    fn take_refresh_request(&self) -> bool {
+        self.shared.refresh_requested.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl<S> Clone for Session<S> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+/// The position of a session as it was last rendered.
+pub(crate) struct Position {
+    pub(crate) index: usize,
+    pub(crate) length: usize,
+}
+
+/// Runs a paginator session with the given view until it is cancelled, returning how it ended.
+///
+/// If the message or its channel is gone, or the bot lost access to it, the session ends with the matching [CancellationType].
+/// Other errors end the session as well, after trying to render it with [CancellationType::Error].
+///
+/// This is the event loop shared by [paginate](crate::paginate), [custom_paginate](crate::custom_paginate) and [paginate_on](crate::paginate_on).
+/// `cx` is passed to the generator and the handler, and everything else goes through the transport.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run<X, T, D, V, VE, C, S, Gen, Fut, Handler, HandlerFut>(
+    cx: X,
+    transport: &T,
+    ids: Arc<[String]>,
+    generator: Gen,
+    handler: Handler,
+    length: usize,
+    index: usize,
+    options: PaginatorOptions<S>,
+    state: S,
+) -> Result<CancellationType, Error>
+where
+    X: Copy,
+    T: Transport,
+    D: 'static,
+    V: View<D, VE, C>,
+    VE: Display + Send + 'static,
+    C: Send + 'static,
+    S: Send + 'static,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+    Handler: Fn(X, C, usize, Session<S>) -> HandlerFut,
+    HandlerFut: Future<Output = Result<CustomAction, Error>> + Send,
+{
+    let id = transport.session_id();
+    let interactions = transport.interactions();
+    let interactions = interactions.as_ref();
+
+    let ids = match &options.signer {
+        Some(signer) => ids.iter().map(|id| signer.sign(id)).collect(),
+        None => ids,
+    };
+
+    let session = Session::new(state, length);
+
+    // Clamped to the length once the first page is rendered.
+    let mut position = Position {
+        index,
+        length: session.length(),
+    };
+
+    let (first_embed, components) = render::<X, D, V, VE, C, S, Gen, Fut>(
+        cx,
+        &generator,
+        &session,
+        &ids,
+        &mut position,
+        CancellationType::NotCancelled,
+        options.truncate,
+    )
+    .await?;
+
+    // The fingerprint of the page the message currently shows.
+    let mut shown = fingerprint(&first_embed, &components);
+
+    let message_id = transport.send(first_embed, components).await?;
+
+    // The part of the record that doesn't change during the session.
+    let record = match &options.store {
+        Some(binding) => {
+            let record = SessionRecord {
+                session_id: id,
+                channel_id: transport.channel_id(),
+                message_id,
+                owner: transport.owner(),
+                index: position.index,
+                length: position.length,
+                state: serde_json::Value::Null,
+            };
+
+            binding.save(&session, record.clone()).await;
+            Some(record)
+        }
+        None => None,
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Event<VE, C>>(options.queue_capacity);
+
+    // An event received while coalescing navigation events, which is handled next.
+    let mut pending = None;
+
+    let latest_presses = LatestPresses::default();
+
+    // Ends with the error of the view if it fails to handle a button press.
+    let mut presses = tokio::spawn(handle_button_presses::<D, VE, C, S, V>(
+        transport.presses(),
+        Arc::new(SessionInteractions::new(transport.interactions())),
+        tx,
+        options.clone(),
+        Arc::clone(&ids),
+        latest_presses.clone(),
+    ));
+
+    // Sessions without a handle get an internal one, through which the registry can close them.
+    let handle = options.handle.clone().unwrap_or_default();
+    let mut commands = handle.take_receiver();
+
+    let _registration = options.registry.as_ref().map(|registry| {
+        registry.register(
+            id,
+            transport.owner(),
+            transport.channel_id(),
+            handle.clone(),
+        )
+    });
+
+    let retry = options.retry;
+
+    let outcome = async {
+        loop {
+            let mut cancellation_type = CancellationType::NotCancelled;
+
+            // Whether the interaction was acknowledged with a deferred update, and how much of the time until then is left.
+            let mut deferred = false;
+            let mut defer_after = options.defer_after;
+
+            // The interaction to respond to with the updated message.
+            // If there is none, the message is edited directly.
+            let interaction = tokio::select! {
+                event = next_event(&mut pending, &mut rx) => {
+                    // The collector only stops without sending a timeout if the view failed to handle a button press.
+                    let Some(event) = event else {
+                        return Err(match (&mut presses).await {
+                            Ok(Err(e)) => e,
+                            Ok(Ok(())) => Error::from("The view failed to handle a button press"),
+                            Err(e) => Error::from(e),
+                        });
+                    };
+
+                    match event {
+                        event @ (Event::ToStart(_)
+                        | Event::Previous(_)
+                        | Event::Next(_)
+                        | Event::ToEnd(_)) => {
+                            let mut navigator = Navigator::new(session.length()).with_index(position.index);
+                            let interaction =
+                                coalesce(interactions, event, &mut rx, &mut pending, &mut navigator, &retry).await?;
+
+                            position.index = navigator.index();
+                            interaction
+                        }
+
+                        // The button was pressed again since, so the result of an older modal is outdated.
+                        Event::Jump(interaction, _) if !latest_presses.is_latest(&interaction) => continue,
+
+                        Event::Jump(interaction, page) => {
+                            let mut navigator = Navigator::new(session.length()).with_index(position.index);
+
+                            if navigator.jump(page) {
+                                position.index = navigator.index();
+                                // The interaction was already used to respond with the modal.
+                                None
+                            } else {
+                                let description = format!("Page {} does not exist.", page.saturating_add(1));
+                                retry.run(|| send_error_embed(interactions, &interaction, &description)).await?;
+                                continue;
+                            }
+                        }
+
+                        Event::Refresh(interaction) => Some(interaction),
+
+                        Event::Custom(interaction, custom) => {
+                            let started = Instant::now();
+                            let handled = handler(cx, custom, position.index, session.clone());
+                            let action = defer_if_slow(interactions, &interaction, handled, defer_after, None, &retry, &mut deferred).await?;
+
+                            // The handler and the render share the time until the interaction has to be acknowledged.
+                            defer_after = defer_after.saturating_sub(started.elapsed());
+
+                            match action {
+                                CustomAction::None => {
+                                    if !deferred {
+                                        acknowledge(interactions, &interaction, &retry).await?;
+                                    }
+                                    continue;
+                                }
+                                CustomAction::Rerender => {}
+                                CustomAction::GoTo(page) => position.index = page,
+                                CustomAction::End => cancellation_type = CancellationType::UserInput,
+                            }
+
+                            Some(interaction)
+                        }
+
+                        Event::CancelledByTimeout => {
+                            cancellation_type = CancellationType::Timeout;
+                            None
+                        }
+
+                        Event::CancelledByUser(interaction) => {
+                            cancellation_type = CancellationType::UserInput;
+                            Some(interaction)
+                        }
+
+                        Event::Error(interaction, e) => {
+                            retry.run(|| send_error_embed(interactions, &interaction, &e)).await?;
+                            continue;
+                        }
+                    }
+                }
+
+                Some(command) = next_command(&mut commands) => match command {
+                    Command::Jump(page) => {
+                        position.index = page;
+                        None
+                    }
+                    Command::Refresh => None,
+                    Command::SetLength(length) => {
+                        // Re-rendered through the session's change notification.
+                        session.set_length(length);
+                        continue;
+                    }
+                    Command::Close(reason) => {
+                        cancellation_type = reason;
+                        None
+                    }
+                },
+
+                _ = cancelled(options.cancellation_token.as_ref()) => {
+                    cancellation_type = CancellationType::Shutdown;
+                    None
+                }
+
+                _ = expired(transport.ephemeral_deadline()) => {
+                    cancellation_type = CancellationType::TokenExpired;
+                    None
+                }
+
+                _ = session.changed() => {
+                    // The generator may have changed the length while rendering,
+                    // in which case the message is already up to date.
+                    if !session.take_refresh_request() && session.length() == position.length {
+                        continue;
+                    }
+
+                    None
+                }
+            };
+
+            // Shown while a slow page is rendered, for the page that is about to be rendered.
+            let loading = options.loading_indicator.then(|| {
+                V::loading_components(
+                    Arc::clone(&ids),
+                    position.index.min(session.length() - 1),
+                    session.length(),
+                )
+            });
+
+            let rendered = async {
+                let rendered = render::<X, D, V, VE, C, S, Gen, Fut>(
+                    cx,
+                    &generator,
+                    &session,
+                    &ids,
+                    &mut position,
+                    cancellation_type,
+                    options.truncate,
+                );
+
+                match &interaction {
+                    Some(interaction) if !deferred => {
+                        defer_if_slow(interactions, interaction, rendered, defer_after, loading, &retry, &mut deferred).await
+                    }
+                    _ => rendered.await,
+                }
+            };
+
+            // The page would be outdated right away if the user navigated further while it was rendered,
+            // so the render is cancelled and the newer event is handled instead. Final renders are never cancelled.
+            let superseded = superseding(
+                &mut rx,
+                &mut pending,
+                cancellation_type == CancellationType::NotCancelled,
+                session.length(),
+                &latest_presses,
+            );
+
+            let (embed, components) = tokio::select! {
+                biased;
+
+                rendered = rendered => rendered?,
+
+                _ = superseded => {
+                    if let Some(interaction) = &interaction && !deferred {
+                        acknowledge(interactions, interaction, &retry).await?;
+                    }
+
+                    continue;
+                }
+            };
+
+            let rendered = fingerprint(&embed, &components);
+
+            // The loading components of a deferred interaction have to be replaced even if the page didn't change.
+            let unchanged = rendered.is_some() && rendered == shown && !(deferred && options.loading_indicator);
+
+            if unchanged {
+                // An edit wouldn't change anything, so the interaction is just acknowledged.
+                if let Some(interaction) = &interaction
+                    && !deferred
+                {
+                    acknowledge(interactions, interaction, &retry).await?;
+                }
+            } else {
+                update(
+                    transport,
+                    interactions,
+                    interaction.as_ref(),
+                    deferred,
+                    &embed,
+                    &components,
+                    &retry,
+                )
+                .await?;
+
+                shown = rendered;
+            }
+
+            handle.emit(match cancellation_type {
+                CancellationType::NotCancelled => NavigationEvent::Rendered {
+                    index: position.index,
+                    length: position.length,
+                },
+                cancellation_type => NavigationEvent::Ended(cancellation_type),
+            });
+
+            let ended = cancellation_type != CancellationType::NotCancelled;
+
+            if let (Some(binding), Some(record)) = (&options.store, &record) {
+                match ended {
+                    true => binding.remove(id).await,
+                    false => {
+                        let record = SessionRecord {
+                            index: position.index,
+                            length: position.length,
+                            ..record.clone()
+                        };
+
+                        binding.save(&session, record).await;
+                    }
+                }
+            }
+
+            if ended {
+                break Ok(cancellation_type);
+            }
+        }
+    }
+    .await;
+
+    // The session doesn't receive presses anymore. Dropping them also removes its route from the dispatcher,
+    // so presses on the finished message aren't routed to it until the timeout.
+    if !presses.is_finished() {
+        presses.abort();
+        presses.await.ok();
+    }
+
+    let Err(e) = outcome else {
+        return outcome;
+    };
+
+    let cancellation_type = CancellationType::from_error(&e);
+
+    // Try to leave the message in a finished state, if it still exists.
+    if cancellation_type == CancellationType::Error
+        && let Ok((embed, components)) = render::<X, D, V, VE, C, S, Gen, Fut>(
+            cx,
+            &generator,
+            &session,
+            &ids,
+            &mut position,
+            cancellation_type,
+            options.truncate,
+        )
+        .await
+    {
+        transport.edit(embed, components).await.ok();
+    }
+
+    handle.emit(NavigationEvent::Ended(cancellation_type));
+
+    if let Some(binding) = &options.store {
+        binding.remove(id).await;
+    }
+
+    match cancellation_type {
+        CancellationType::Error => Err(e),
+        cancellation_type => Ok(cancellation_type),
+    }
+}
+
+/// Waits until the cancellation token of the session is cancelled, if it has one.
+async fn cancelled(token: Option<&CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Waits until the given deadline, if there is one.
+async fn expired(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Receives the next event, starting with the pending one.
+async fn next_event<E, C>(
+    pending: &mut Option<Event<E, C>>,
+    rx: &mut Receiver<Event<E, C>>,
+) -> Option<Event<E, C>> {
+    match pending.take() {
+        Some(event) => Some(event),
+        None => rx.recv().await,
+    }
+}
+
+/// Waits for a navigation event, which supersedes the page being rendered.
+///
+/// The received event is kept as the pending event. If it isn't a navigation event, it is handled after the render
+/// and no further events are received until then. Outdated jumps and jumps to pages that don't exist out of the
+/// `length` pages are dropped without rendering, so they don't supersede the render either.
+async fn superseding<E, C>(
+    rx: &mut Receiver<Event<E, C>>,
+    pending: &mut Option<Event<E, C>>,
+    enabled: bool,
+    length: usize,
+    latest_presses: &LatestPresses,
+) {
+    if enabled
+        && pending.is_none()
+        && let Some(event) = rx.recv().await
+    {
+        let navigation = match &event {
+            Event::ToStart(_) | Event::Previous(_) | Event::Next(_) | Event::ToEnd(_) => true,
+            Event::Jump(press, page) => {
+                latest_presses.is_latest(press) && Navigator::new(length).contains(*page)
+            }
+            _ => false,
+        };
+
+        *pending = Some(event);
+
+        if navigation {
+            return;
+        }
+    }
+
+    std::future::pending().await
+}
+
+/// Applies the given navigation event, and the navigation events queued right behind it, to the navigator, so they are rendered at once.
+///
+/// Returns the interaction of the last event, which is responded to with the rendered page. The interactions of the others are acknowledged.
+/// The first queued event that isn't a navigation event is kept as the pending event.
+async fn coalesce<E, C>(
+    interactions: &dyn Interactions,
+    event: Event<E, C>,
+    rx: &mut Receiver<Event<E, C>>,
+    pending: &mut Option<Event<E, C>>,
+    navigator: &mut Navigator,
+    retry: &RetryPolicy,
+) -> Result<Option<ComponentInteraction>, Error> {
+    let mut interaction = None::<ComponentInteraction>;
+    let mut event = event;
+
+    loop {
+        let (navigation, next) = match event {
+            Event::ToStart(next) => (Navigation::First, next),
+            Event::Previous(next) => (Navigation::Previous, next),
+            Event::Next(next) => (Navigation::Next, next),
+            Event::ToEnd(next) => (Navigation::Last, next),
+            event => {
+                *pending = Some(event);
+                break;
+            }
+        };
+
+        navigator.apply(navigation);
+
+        if let Some(superseded) = interaction.replace(next) {
+            acknowledge(interactions, &superseded, retry).await?;
+        }
+
+        match rx.try_recv() {
+            Ok(next) => event = next,
+            Err(_) => break,
+        }
+    }
+
+    Ok(interaction)
+}
+
+/// Receives the next command sent through the handle of the session, if it has one.
+async fn next_command(commands: &mut Option<UnboundedReceiver<Command>>) -> Option<Command> {
+    match commands {
+        Some(commands) => commands.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Renders the page at the current position.
+///
+/// The index is clamped to the length of the session before and after calling the generator,
+/// since the generator may change the length. In the latter case, the generator is called again for the clamped index.
+/// The page is [checked](limits::check) against Discord's limits, truncating it if `truncate` is set.
+pub(crate) async fn render<X, D, V, VE, C, S, Gen, Fut>(
+    cx: X,
+    generator: &Gen,
+    session: &Session<S>,
+    ids: &Arc<[String]>,
+    position: &mut Position,
+    cancellation_type: CancellationType,
+    truncate: bool,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), Error>
+where
+    X: Copy,
+    V: View<D, VE, C>,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+{
+    position.index = position.index.min(session.length() - 1);
+
+    let embed = loop {
+        let embed = generator(cx, position.index, cancellation_type, session.clone()).await?;
+
+        let length = session.length();
+
+        if position.index < length {
+            position.length = length;
+            break embed;
+        }
+
+        position.index = length - 1;
+    };
+
+    let components = V::rerender_components(
+        Arc::clone(ids),
+        position.index,
+        position.length,
+        cancellation_type != CancellationType::NotCancelled,
+    );
+
+    let embed = limits::check(embed, &components, position.index, truncate)?;
+
+    Ok((embed, components))
+}
+
+/// Awaits the render of the page, or the custom event handler, for the given interaction.
+///
+/// If it takes longer than `defer_after`, the interaction is acknowledged with a deferred update first, showing the loading components if given,
+/// since Discord requires a response within 3 seconds. `deferred` is set once the interaction was acknowledged,
+/// in which case the message has to be updated by editing the response.
+async fn defer_if_slow<T>(
+    interactions: &dyn Interactions,
+    interaction: &ComponentInteraction,
+    rendered: impl Future<Output = Result<T, Error>>,
+    defer_after: Duration,
+    loading: Option<Vec<CreateActionRow>>,
+    retry: &RetryPolicy,
+    deferred: &mut bool,
+) -> Result<T, Error> {
+    tokio::pin!(rendered);
+
+    if let Ok(rendered) = tokio::time::timeout(defer_after, &mut rendered).await {
+        return rendered;
+    }
+
+    let response = match loading {
+        Some(components) => CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::default().components(components),
+        ),
+        None => CreateInteractionResponse::Acknowledge,
+    };
+
+    retry
+        .run(|| interactions.respond(interaction, response.clone()))
+        .await?;
+    *deferred = true;
+
+    rendered.await
+}
+
+async fn handle_button_presses<D, E, C, S, V: View<D, E, C>>(
+    presses: BoxStream<'static, ComponentInteraction>,
+    interactions: Arc<dyn Interactions>,
+    tx: Sender<Event<E, C>>,
+    options: PaginatorOptions<S>,
+    ids: Arc<[String]>,
+    latest_presses: LatestPresses,
+) -> Result<(), Error> {
+    let mut presses = presses
+        .take_until(tokio::time::sleep(options.timeout))
+        .boxed();
+
+    let mut last_press = None::<Instant>;
+
+    while let Some(press) = presses.next().await {
+        if let Some(signer) = &options.signer
+            && !signer.verify(&press.data.custom_id)
+        {
+            tracing::warn!(
+                custom_id = press.data.custom_id,
+                user_id = %press.user.id,
+                "Rejected a paginator interaction with an invalid signature"
+            );
+            continue;
+        }
+
+        let cooling_down = options
+            .cooldown
+            .zip(last_press)
+            .is_some_and(|(cooldown, last_press)| last_press.elapsed() < cooldown);
+
+        // Presses that can't be handled are acknowledged, so they don't show up as failed interactions.
+        if cooling_down || tx.capacity() == 0 {
+            interactions
+                .respond(&press, CreateInteractionResponse::Acknowledge)
+                .await
+                .ok();
+            continue;
+        }
+
+        last_press = Some(Instant::now());
+        latest_presses.record(&press);
+
+        V::on_button_press(
+            Arc::clone(&interactions),
+            press,
+            tx.clone(),
+            Arc::clone(&ids),
+        )
+        .await?;
+    }
+
+    tx.send(Event::CancelledByTimeout).await.unwrap_or_default();
+    Ok(())
+}
+
+/// The latest press of every button of a session, in the order the interactions were created.
+///
+/// Results that arrive after the button was pressed again, like the input of a modal that was opened again, are
+/// outdated and dropped.
+#[derive(Clone, Default)]
+struct LatestPresses(Arc<std::sync::Mutex<HashMap<String, InteractionId>>>);
+
+impl LatestPresses {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, InteractionId>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn record(&self, press: &ComponentInteraction) {
+        let mut latest = self.lock();
+        let id = latest
+            .entry(press.data.custom_id.clone())
+            .or_insert(press.id);
+
+        // Interaction ids are snowflakes, so a newer press has a greater id.
+        *id = press.id.max(*id);
+    }
+
+    /// Whether the button wasn't pressed again after the given press.
+    fn is_latest(&self, press: &ComponentInteraction) -> bool {
+        self.lock()
+            .get(&press.data.custom_id)
+            .is_none_or(|latest| *latest <= press.id)
+    }
+}
+
+/// The [Interactions] of a session as its view sees them, which keep at most one modal of the session open.
+///
+/// Opening a modal closes the one still waiting for its submission, which returns `None` right away instead of
+/// once it times out. A modal for a press older than the open one isn't opened at all, the press is only acknowledged.
+struct SessionInteractions {
+    inner: Arc<dyn Interactions>,
+    open_modal: std::sync::Mutex<Option<(InteractionId, oneshot::Sender<()>)>>,
+}
+
+impl SessionInteractions {
+    fn new(inner: Arc<dyn Interactions>) -> Self {
+        Self {
+            inner,
+            open_modal: std::sync::Mutex::default(),
+        }
+    }
+}
+
+impl Interactions for SessionInteractions {
+    fn respond<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: CreateInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.inner.respond(interaction, response)
+    }
+
+    fn edit_response<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: EditInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.inner.edit_response(interaction, response)
+    }
+
+    fn followup<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        followup: CreateInteractionResponseFollowup,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.inner.followup(interaction, followup)
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        message: EditMessage,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.inner.edit_message(interaction, message)
+    }
+
+    fn quick_modal<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        modal: CreateQuickModal,
+    ) -> BoxFuture<'a, Result<Option<Vec<String>>, Error>> {
+        let (close, closed) = oneshot::channel();
+
+        let outdated = {
+            let mut open_modal = self
+                .open_modal
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            // Interaction ids are snowflakes, so the press of a newer modal has a greater id.
+            // Replacing the sender of the open modal drops it, which closes the modal.
+            match &*open_modal {
+                Some((open, _)) if *open > interaction.id => true,
+                _ => {
+                    *open_modal = Some((interaction.id, close));
+                    false
+                }
+            }
+        };
+
+        Box::pin(async move {
+            if outdated {
+                self.inner
+                    .respond(interaction, CreateInteractionResponse::Acknowledge)
+                    .await?;
+                return Ok(None);
+            }
+
+            tokio::select! {
+                inputs = self.inner.quick_modal(interaction, modal) => inputs,
+                _ = closed => Ok(None),
+            }
+        })
+    }
+}
+
+/// A hash of the rendered page, used to skip edits that wouldn't change the message.
+fn fingerprint(embed: &CreateEmbed, components: &[CreateActionRow]) -> Option<u64> {
+    let json = serde_json::to_vec(&(embed, components)).ok()?;
+
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Acknowledges the interaction without updating the message.
+async fn acknowledge(
+    interactions: &dyn Interactions,
+    interaction: &ComponentInteraction,
+    retry: &RetryPolicy,
+) -> Result<(), Error> {
+    retry
+        .run(|| interactions.respond(interaction, CreateInteractionResponse::Acknowledge))
+        .await
+}
+
+/// Updates the message with the rendered page, through the interaction if there is one.
+///
+/// If the interaction can't be responded to anymore, e.g. because the page took too long to render
+/// without being deferred, the message is edited directly instead.
+#[allow(clippy::too_many_arguments)]
+async fn update(
+    transport: &impl Transport,
+    interactions: &dyn Interactions,
+    interaction: Option<&ComponentInteraction>,
+    deferred: bool,
+    embed: &CreateEmbed,
+    components: &[CreateActionRow],
+    retry: &RetryPolicy,
+) -> Result<(), Error> {
+    let edit = move || transport.edit(embed.clone(), components.to_vec());
+
+    let result = match interaction {
+        Some(interaction) if deferred => {
+            retry
+                .run(move || {
+                    let response = EditInteractionResponse::new()
+                        .embed(embed.clone())
+                        .components(components.to_vec());
+
+                    interactions.edit_response(interaction, response)
+                })
+                .await
+        }
+        Some(interaction) => {
+            retry
+                .run(move || {
+                    let reply = CreateInteractionResponseMessage::default()
+                        .embed(embed.clone())
+                        .components(components.to_vec());
+
+                    interactions
+                        .respond(interaction, CreateInteractionResponse::UpdateMessage(reply))
+                })
+                .await
+        }
+        None => return retry.run(edit).await,
+    };
+
+    match result {
+        Err(e) if is_expired_interaction(&e) => retry.run(edit).await,
+        result => result,
+    }
+}
+
+pub(crate) async fn send_error_embed(
+    interactions: &dyn Interactions,
+    interaction: &ComponentInteraction,
+    description: impl Display,
+) -> Result<(), Error> {
+    let embed = CreateEmbed::new()
+        .title("Error")
+        .description(description.to_string())
+        .color(Color::RED);
+
+    interactions
+        .followup(
+            interaction,
+            CreateInteractionResponseFollowup::default()
+                .embed(embed)
+                .ephemeral(true),
+        )
+        .await
+}
@@ -0,0 +1,56 @@
+use {
+    hmac::{Hmac, Mac},
+    sha2::Sha256,
+};
+
+/// The number of bytes of the HMAC appended to component ids. Truncated to stay within Discord's limit of 100 characters.
+const SIGNATURE_LENGTH: usize = 8;
+
+/// Signs component ids with a secret held by the bot, so that modified clients can't send interactions with arbitrary ids.
+///
+/// Every id is suffixed with `.` and a truncated HMAC-SHA256 of the id. Interactions with a missing or invalid signature
+/// are rejected and logged before they reach [View::on_button_press](crate::View::on_button_press).
+///
+/// Keep the secret stable across restarts if you use [persistent paginators](crate::PersistentPaginators),
+/// otherwise the buttons of old messages are rejected.
+#[derive(Clone)]
+pub struct Signer {
+    mac: Hmac<Sha256>,
+}
+
+impl Signer {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(secret.as_ref()).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    /// Appends the signature to the given id.
+    pub fn sign(&self, id: &str) -> String {
+        let mut mac = self.mac.clone();
+        mac.update(id.as_bytes());
+
+        let signature = mac.finalize().into_bytes();
+
+        format!("{id}.{}", hex::encode(&signature[..SIGNATURE_LENGTH]))
+    }
+
+    /// Checks whether the given custom id carries a valid signature.
+    pub fn verify(&self, custom_id: &str) -> bool {
+        let Some((id, signature)) = custom_id.rsplit_once('.') else {
+            return false;
+        };
+
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        if signature.len() != SIGNATURE_LENGTH {
+            return false;
+        }
+
+        let mut mac = self.mac.clone();
+        mac.update(id.as_bytes());
+        mac.verify_truncated_left(&signature).is_ok()
+    }
+}
@@ -0,0 +1,159 @@
+use {
+    crate::{
+        Error,
+        cancellation_type::CancellationType,
+        custom_paginator::PaginationInfo,
+        session::{self, Position, Session},
+        view::{View, default_view::DefaultView},
+    },
+    poise::serenity_prelude::CreateEmbed,
+    serde::Serialize,
+    serde_json::Value,
+    std::{path::Path, sync::Arc},
+};
+
+/// The session id the component ids of a [snapshot] start with.
+const SNAPSHOT_SESSION_ID: u64 = 1;
+
+/// A page as a paginator would send it: its embed and components, as the JSON Discord receives.
+///
+/// Created through [snapshot] and [custom_snapshot], for golden snapshot tests of generators and views.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// The index of the page, which is clamped to the length like during a session.
+    pub index: usize,
+
+    /// The number of pages, including changes made by the generator.
+    pub length: usize,
+
+    pub embed: Value,
+
+    pub components: Value,
+}
+
+impl Snapshot {
+    /// Asserts that the snapshot matches the golden file at the given path, e.g. `tests/snapshots/first_page.json`.
+    ///
+    /// If the file doesn't exist or the `UPDATE_SNAPSHOTS` environment variable is set, the file is written instead.
+    ///
+    /// # Panics
+    /// Panics if the snapshot doesn't match, or the file can't be read or written.
+    pub fn assert_matches(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).expect("Snapshots are valid JSON") + "\n";
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).expect("The snapshot directory can be created");
+            }
+
+            std::fs::write(path, json).expect("The snapshot can be written");
+            return;
+        }
+
+        let golden = std::fs::read_to_string(path).expect("The snapshot can be read");
+
+        assert!(
+            golden == json,
+            "The snapshot doesn't match {}, set UPDATE_SNAPSHOTS to update it\n\nexpected:\n{golden}\nactual:\n{json}",
+            path.display()
+        );
+    }
+}
+
+/// Renders the page at the given index like [paginate](crate::paginate) would, without sending it.
+///
+/// The generator receives `cx` instead of the command context, like with [paginate_on](crate::paginate_on).
+/// The component ids are those of a session with the id 1. Pages exceeding Discord's limits fail like they would
+/// during a session, without being truncated.
+pub async fn snapshot<X, Gen, Fut, S>(
+    cx: X,
+    generator: Gen,
+    index: usize,
+    length: usize,
+    cancellation_type: CancellationType,
+    state: S,
+) -> Result<Snapshot, Error>
+where
+    X: Copy,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+{
+    render::<X, (), DefaultView, Error, (), S, Gen, Fut>(
+        cx,
+        generator,
+        <DefaultView>::ids(SNAPSHOT_SESSION_ID),
+        index,
+        length,
+        cancellation_type,
+        state,
+    )
+    .await
+}
+
+/// Renders the page at the given index like [custom_paginate](crate::custom_paginate) would, without sending it.
+///
+/// The component ids are passed in, as [View::create_ids](crate::View::create_ids) would create them.
+pub async fn custom_snapshot<P, X, Fut, S>(
+    cx: X,
+    generator: impl Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    ids: Arc<[String]>,
+    index: usize,
+    length: usize,
+    cancellation_type: CancellationType,
+    state: S,
+) -> Result<Snapshot, Error>
+where
+    P: PaginationInfo,
+    X: Copy,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+{
+    render::<X, P::PoiseData, P::View, P::PoiseError, P::CustomEvent, S, _, Fut>(
+        cx,
+        generator,
+        ids,
+        index,
+        length,
+        cancellation_type,
+        state,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn render<X, D, V, VE, C, S, Gen, Fut>(
+    cx: X,
+    generator: Gen,
+    ids: Arc<[String]>,
+    index: usize,
+    length: usize,
+    cancellation_type: CancellationType,
+    state: S,
+) -> Result<Snapshot, Error>
+where
+    X: Copy,
+    V: View<D, VE, C>,
+    Gen: Fn(X, usize, CancellationType, Session<S>) -> Fut,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send,
+{
+    let session = Session::new(state, length);
+    let mut position = Position { index, length };
+
+    let (embed, components) = session::render::<X, D, V, VE, C, S, Gen, Fut>(
+        cx,
+        &generator,
+        &session,
+        &ids,
+        &mut position,
+        cancellation_type,
+        false,
+    )
+    .await?;
+
+    Ok(Snapshot {
+        index: position.index,
+        length: position.length,
+        embed: serde_json::to_value(embed)?,
+        components: serde_json::to_value(components)?,
+    })
+}
@@ -0,0 +1,207 @@
+use {
+    crate::{Error, session::Session},
+    poise::serenity_prelude::{ChannelId, MessageId, UserId, futures::future::BoxFuture},
+    serde::{Deserialize, Serialize, de::DeserializeOwned},
+    std::{
+        collections::HashMap,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// A snapshot of a paginator session, saved to a [SessionStore] on every change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// The id of the session, which is the id of the command invocation.
+    pub session_id: u64,
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub owner: UserId,
+    pub index: usize,
+    pub length: usize,
+
+    /// The state of the session, serialized with `serde_json`.
+    pub state: serde_json::Value,
+}
+
+impl SessionRecord {
+    /// Deserializes the state of the session.
+    pub fn state<S: DeserializeOwned>(&self) -> Result<S, Error> {
+        Ok(S::deserialize(&self.state)?)
+    }
+}
+
+/// A store for the [records](SessionRecord) of paginator sessions.
+///
+/// Sessions are saved after every change and removed once they end, so after a restart the store contains the sessions
+/// that were still open. These can be listed, inspected and resumed through [resume](crate::resume) or
+/// [custom_resume](crate::custom_resume), e.g. by a different process or shard.
+///
+/// Failing to save or remove a record is logged, but doesn't end the session.
+pub trait SessionStore: Send + Sync + 'static {
+    /// Saves the record, replacing an existing record of the same session.
+    fn save(&self, record: SessionRecord) -> BoxFuture<'_, Result<(), Error>>;
+
+    /// Loads the record of the given session.
+    fn load(&self, session_id: u64) -> BoxFuture<'_, Result<Option<SessionRecord>, Error>>;
+
+    /// Removes the record of the given session.
+    fn remove(&self, session_id: u64) -> BoxFuture<'_, Result<(), Error>>;
+
+    /// Lists the records of all sessions.
+    fn list(&self) -> BoxFuture<'_, Result<Vec<SessionRecord>, Error>>;
+}
+
+/// A [SessionStore] keeping the records in memory. Records don't survive restarts.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySessionStore {
+    records: Arc<Mutex<HashMap<u64, SessionRecord>>>,
+}
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, HashMap<u64, SessionRecord>> {
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn save(&self, record: SessionRecord) -> BoxFuture<'_, Result<(), Error>> {
+        self.records().insert(record.session_id, record);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn load(&self, session_id: u64) -> BoxFuture<'_, Result<Option<SessionRecord>, Error>> {
+        let record = self.records().get(&session_id).cloned();
+        Box::pin(async { Ok(record) })
+    }
+
+    fn remove(&self, session_id: u64) -> BoxFuture<'_, Result<(), Error>> {
+        self.records().remove(&session_id);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn list(&self) -> BoxFuture<'_, Result<Vec<SessionRecord>, Error>> {
+        let records = self.records().values().cloned().collect();
+        Box::pin(async { Ok(records) })
+    }
+}
+
+/// A [SessionStore] keeping the records in a JSON file.
+///
+/// The records are cached in memory and the whole file is rewritten on every change,
+/// so this is meant for bots with a moderate number of concurrent sessions.
+#[derive(Debug)]
+pub struct FileSessionStore {
+    path: PathBuf,
+    records: tokio::sync::Mutex<HashMap<u64, SessionRecord>>,
+}
+
+impl FileSessionStore {
+    /// Opens the store at the given path, loading the records of an existing file.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+
+        let records = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice::<Vec<SessionRecord>>(&contents)?
+                .into_iter()
+                .map(|record| (record.session_id, record))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path,
+            records: tokio::sync::Mutex::new(records),
+        })
+    }
+
+    /// Writes the records to a temporary file first, so a crash mid-write doesn't corrupt the store.
+    async fn write(&self, records: &HashMap<u64, SessionRecord>) -> Result<(), Error> {
+        let contents = serde_json::to_vec_pretty(&records.values().collect::<Vec<_>>())?;
+
+        let temporary = self.path.with_extension("tmp");
+        tokio::fs::write(&temporary, contents).await?;
+        tokio::fs::rename(&temporary, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn save(&self, record: SessionRecord) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let mut records = self.records.lock().await;
+            records.insert(record.session_id, record);
+            self.write(&records).await
+        })
+    }
+
+    fn load(&self, session_id: u64) -> BoxFuture<'_, Result<Option<SessionRecord>, Error>> {
+        Box::pin(async move { Ok(self.records.lock().await.get(&session_id).cloned()) })
+    }
+
+    fn remove(&self, session_id: u64) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let mut records = self.records.lock().await;
+
+            if records.remove(&session_id).is_some() {
+                self.write(&records).await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn list(&self) -> BoxFuture<'_, Result<Vec<SessionRecord>, Error>> {
+        Box::pin(async move { Ok(self.records.lock().await.values().cloned().collect()) })
+    }
+}
+
+/// A [SessionStore] along with the function serializing the state of the session.
+pub(crate) struct StoreBinding<S> {
+    pub(crate) store: Arc<dyn SessionStore>,
+    pub(crate) serialize: fn(&S) -> Result<serde_json::Value, serde_json::Error>,
+}
+
+impl<S> StoreBinding<S> {
+    /// Saves the given record with the current state of the session.
+    /// Failures are logged, since they shouldn't end the session.
+    pub(crate) async fn save(&self, session: &Session<S>, mut record: SessionRecord) {
+        let state = (self.serialize)(&*session.state().await);
+
+        let result = match state {
+            Ok(state) => {
+                record.state = state;
+                self.store.save(record).await
+            }
+            Err(e) => Err(e.into()),
+        };
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to save the record of a paginator session: {e}");
+        }
+    }
+
+    /// Removes the record of the given session. Failures are logged.
+    pub(crate) async fn remove(&self, session_id: u64) {
+        if let Err(e) = self.store.remove(session_id).await {
+            tracing::warn!("Failed to remove the record of a paginator session: {e}");
+        }
+    }
+}
+
+impl<S> Clone for StoreBinding<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+            serialize: self.serialize,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use {
+    super::{Interactions, Transport},
+    crate::{Error, dispatcher::InteractionDispatcher, message::PaginatorMessage},
+    poise::{
+        ApplicationContext,
+        serenity_prelude::{
+            ChannelId, ComponentInteraction, ComponentInteractionCollector, CreateActionRow,
+            CreateEmbed, MessageId, UserId,
+            futures::{
+                StreamExt,
+                future::BoxFuture,
+                stream::{self, BoxStream},
+            },
+        },
+    },
+    std::sync::{Arc, OnceLock},
+    tokio::time::Instant,
+};
+
+/// The transport of paginators started from a command, talking to Discord through serenity.
+pub(crate) struct DiscordTransport<'a, D, E> {
+    ctx: ApplicationContext<'a, D, E>,
+    dispatcher: Option<InteractionDispatcher>,
+    message: OnceLock<PaginatorMessage<'a>>,
+}
+
+impl<'a, D, E> DiscordTransport<'a, D, E> {
+    /// Creates the transport for the command, receiving the button presses through the dispatcher if given.
+    pub(crate) fn new(
+        ctx: ApplicationContext<'a, D, E>,
+        dispatcher: Option<InteractionDispatcher>,
+    ) -> Self {
+        Self {
+            ctx,
+            dispatcher,
+            message: OnceLock::new(),
+        }
+    }
+
+    fn message(&self) -> Result<&PaginatorMessage<'a>, Error> {
+        self.message
+            .get()
+            .ok_or_else(|| Error::from("The paginator message hasn't been sent yet"))
+    }
+}
+
+impl<D, E> Transport for DiscordTransport<'_, D, E>
+where
+    D: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    fn session_id(&self) -> u64 {
+        self.ctx.id()
+    }
+
+    fn owner(&self) -> UserId {
+        self.ctx.author().id
+    }
+
+    fn channel_id(&self) -> ChannelId {
+        self.ctx.channel_id()
+    }
+
+    fn send(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<MessageId, Error>> {
+        Box::pin(async move {
+            let message = PaginatorMessage::send(self.ctx, embed, components).await?;
+            let id = message.id();
+
+            self.message
+                .set(message)
+                .map_err(|_| Error::from("The paginator message was already sent"))?;
+
+            Ok(id)
+        })
+    }
+
+    fn edit(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move { self.message()?.edit(self.ctx, embed, components).await })
+    }
+
+    fn presses(&self) -> BoxStream<'static, ComponentInteraction> {
+        let (id, owner, channel_id) = (self.session_id(), self.owner(), self.channel_id());
+
+        match &self.dispatcher {
+            Some(dispatcher) => {
+                let subscription = dispatcher.subscribe(id, owner, channel_id);
+
+                stream::unfold(subscription, |mut subscription| async move {
+                    let press = subscription.next().await?;
+                    Some((press, subscription))
+                })
+                .boxed()
+            }
+            None => {
+                let id = id.to_string();
+
+                ComponentInteractionCollector::new(self.ctx.serenity_context())
+                    .author_id(owner)
+                    .channel_id(channel_id)
+                    .filter(move |interaction| interaction.data.custom_id.starts_with(&id))
+                    .stream()
+                    .boxed()
+            }
+        }
+    }
+
+    fn interactions(&self) -> Arc<dyn Interactions> {
+        Arc::new(self.ctx.serenity_context().clone())
+    }
+
+    fn ephemeral_deadline(&self) -> Option<Instant> {
+        self.message.get()?.ephemeral_deadline()
+    }
+}
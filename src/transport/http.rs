@@ -0,0 +1,236 @@
+use {
+    super::{Interactions, Transport},
+    crate::{Error, dispatcher::InteractionDispatcher, message::token_expires_at},
+    poise::serenity_prelude::{
+        ChannelId, CommandInteraction, ComponentInteraction, CreateActionRow, CreateEmbed,
+        CreateInteractionResponse, CreateInteractionResponseFollowup,
+        CreateInteractionResponseMessage, CreateQuickModal, EditInteractionResponse, EditMessage,
+        Http, MessageFlags, MessageId, UserId,
+        futures::{
+            StreamExt,
+            future::BoxFuture,
+            stream::{self, BoxStream},
+        },
+    },
+    std::sync::{Arc, OnceLock},
+    tokio::time::Instant,
+};
+
+/// A [Transport] talking to Discord through serenity's [Http] only, without poise or a gateway connection.
+///
+/// This runs paginators for bots that receive their interactions through an HTTP endpoint instead of the gateway, through
+/// [paginate_on](crate::paginate_on) or [custom_paginate_on](crate::custom_paginate_on). The first page is sent as the
+/// response to the command interaction, and the button presses are received through the [InteractionDispatcher], which
+/// has to be fed the interactions the bot receives. Pointing serenity's [Http] at a local server through its proxy
+/// setting also makes it suitable for testing the exact requests a paginator sends.
+///
+/// Like with [paginate](crate::paginate), the message is edited through the token of the command interaction while it is
+/// valid and through the channel afterwards. Sessions with an [ephemeral](HttpTransport::ephemeral) message end with
+/// [CancellationType::TokenExpired](crate::CancellationType::TokenExpired) shortly before the token expires.
+///
+/// Modals need a gateway connection, so pressing the "Jump to page" button of the [DefaultView](crate::DefaultView) reports an error.
+pub struct HttpTransport {
+    http: Arc<Http>,
+    interaction: CommandInteraction,
+    dispatcher: InteractionDispatcher,
+    ephemeral: bool,
+    token_expires_at: Instant,
+    message: OnceLock<SentMessage>,
+}
+
+/// The message sent in response to the command interaction.
+struct SentMessage {
+    id: MessageId,
+    ephemeral: bool,
+}
+
+impl HttpTransport {
+    /// Creates the transport for the command interaction, which must not have been responded to yet.
+    pub fn new(
+        http: Arc<Http>,
+        interaction: CommandInteraction,
+        dispatcher: InteractionDispatcher,
+    ) -> Self {
+        Self {
+            http,
+            token_expires_at: token_expires_at(interaction.id),
+            interaction,
+            dispatcher,
+            ephemeral: false,
+            message: OnceLock::new(),
+        }
+    }
+
+    /// Sends the message as an ephemeral message, which only the user running the command can see.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    fn message(&self) -> Result<&SentMessage, Error> {
+        self.message
+            .get()
+            .ok_or_else(|| Error::from("The paginator message hasn't been sent yet"))
+    }
+}
+
+impl Transport for HttpTransport {
+    fn session_id(&self) -> u64 {
+        self.interaction.id.get()
+    }
+
+    fn owner(&self) -> UserId {
+        self.interaction.user.id
+    }
+
+    fn channel_id(&self) -> ChannelId {
+        self.interaction.channel_id
+    }
+
+    fn send(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<MessageId, Error>> {
+        Box::pin(async move {
+            let mut message = CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(components);
+
+            if self.ephemeral {
+                message = message.ephemeral(true);
+            }
+
+            self.interaction
+                .create_response(&self.http, CreateInteractionResponse::Message(message))
+                .await?;
+
+            let message = self.interaction.get_response(&self.http).await?;
+            let ephemeral = self.ephemeral
+                || message
+                    .flags
+                    .is_some_and(|flags| flags.contains(MessageFlags::EPHEMERAL));
+
+            self.message
+                .set(SentMessage {
+                    id: message.id,
+                    ephemeral,
+                })
+                .map_err(|_| Error::from("The paginator message was already sent"))?;
+
+            Ok(message.id)
+        })
+    }
+
+    fn edit(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let message = self.message()?;
+
+            // Like with commands, the message is edited through the channel once the token expired.
+            // Ephemeral messages can only be edited through the token.
+            if message.ephemeral || Instant::now() < self.token_expires_at {
+                let response = EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(components);
+
+                self.interaction.edit_response(&self.http, response).await?;
+            } else {
+                self.interaction
+                    .channel_id
+                    .edit_message(
+                        &self.http,
+                        message.id,
+                        EditMessage::new().embed(embed).components(components),
+                    )
+                    .await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn presses(&self) -> BoxStream<'static, ComponentInteraction> {
+        let subscription =
+            self.dispatcher
+                .subscribe(self.session_id(), self.owner(), self.channel_id());
+
+        stream::unfold(subscription, |mut subscription| async move {
+            let press = subscription.next().await?;
+            Some((press, subscription))
+        })
+        .boxed()
+    }
+
+    fn interactions(&self) -> Arc<dyn Interactions> {
+        Arc::clone(&self.http) as Arc<dyn Interactions>
+    }
+
+    fn ephemeral_deadline(&self) -> Option<Instant> {
+        let message = self.message.get()?;
+        message.ephemeral.then_some(self.token_expires_at)
+    }
+}
+
+impl Interactions for Http {
+    fn respond<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: CreateInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move { Ok(interaction.create_response(self, response).await?) })
+    }
+
+    fn edit_response<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: EditInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            interaction.edit_response(self, response).await?;
+            Ok(())
+        })
+    }
+
+    fn followup<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        followup: CreateInteractionResponseFollowup,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            interaction.create_followup(self, followup).await?;
+            Ok(())
+        })
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        message: EditMessage,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            interaction
+                .channel_id
+                .edit_message(self, interaction.message.id, message)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn quick_modal<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        _modal: CreateQuickModal,
+    ) -> BoxFuture<'a, Result<Option<Vec<String>>, Error>> {
+        Box::pin(async move {
+            // Acknowledged, so the error can be reported through a followup.
+            self.respond(interaction, CreateInteractionResponse::Acknowledge)
+                .await?;
+
+            Err(Error::from("Modals need a gateway connection"))
+        })
+    }
+}
@@ -0,0 +1,420 @@
+use {
+    super::{Interactions, Transport},
+    crate::Error,
+    poise::serenity_prelude::{
+        ChannelId, ComponentInteraction, CreateActionRow, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseFollowup, CreateQuickModal, EditInteractionResponse, EditMessage,
+        MessageId, UserId,
+        futures::{
+            StreamExt,
+            future::BoxFuture,
+            stream::{self, BoxStream},
+        },
+    },
+    serde_json::{Value, json},
+    std::sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    tokio::sync::{
+        Mutex,
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+    },
+};
+
+/// The id of the message sent through a [MockTransport].
+const MESSAGE_ID: u64 = 1;
+
+/// A scripted, in-memory [Transport] for testing paginators without Discord.
+///
+/// Button presses and modal submissions are scripted through [MockTransport::press] and [MockTransport::submit_modal].
+/// Everything the session sends is recorded as the JSON that would be sent to Discord, and can be awaited in order through
+/// [MockTransport::next_request]. [MockTransport::message] is the message as the user currently sees it.
+///
+/// Run a session on it through [paginate_on](crate::paginate_on), preferably with the tokio clock paused
+/// (`#[tokio::test(start_paused = true)]`), so timeouts elapse instantly once the session is idle.
+///
+/// The session has the id 1, and both its owner and its channel have the id 1 as well. Cloning the transport is cheap
+/// and all clones script and record the same session.
+#[derive(Clone)]
+pub struct MockTransport {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    presses: UnboundedSender<ComponentInteraction>,
+    press_receiver: std::sync::Mutex<Option<UnboundedReceiver<ComponentInteraction>>>,
+    modals: UnboundedSender<Option<Vec<String>>>,
+    modal_receiver: Mutex<UnboundedReceiver<Option<Vec<String>>>>,
+    requests: UnboundedSender<MockRequest>,
+    request_receiver: Mutex<UnboundedReceiver<MockRequest>>,
+    message: std::sync::Mutex<Option<MockMessage>>,
+    next_interaction_id: AtomicU64,
+}
+
+/// A request a session sent through a [MockTransport].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockRequest {
+    /// The first page was sent.
+    Send(MockMessage),
+
+    /// The message was edited without responding to an interaction.
+    Edit(MockMessage),
+
+    /// The interaction on the component with the given id was responded to.
+    Respond { custom_id: String, response: Value },
+
+    /// The response to the interaction on the component with the given id was edited.
+    EditResponse { custom_id: String, response: Value },
+
+    /// A followup message was sent to the interaction on the component with the given id.
+    Followup { custom_id: String, followup: Value },
+
+    /// The interaction on the component with the given id was responded to with a modal.
+    Modal { custom_id: String },
+}
+
+impl MockRequest {
+    /// The JSON of the response, if this is a response to an interaction.
+    pub fn response(&self) -> Option<&Value> {
+        match self {
+            Self::Respond { response, .. } | Self::EditResponse { response, .. } => Some(response),
+            _ => None,
+        }
+    }
+
+    /// Whether this acknowledges an interaction without updating the message.
+    pub fn is_acknowledgement(&self) -> bool {
+        self.response()
+            .is_some_and(|response| response["type"] == 6)
+    }
+}
+
+/// A message as it was sent through a [MockTransport].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MockMessage {
+    /// The JSON of the embed.
+    pub embed: Value,
+
+    /// The JSON of the action rows.
+    pub components: Value,
+}
+
+impl MockMessage {
+    fn new(embed: &CreateEmbed, components: &[CreateActionRow]) -> Self {
+        Self {
+            embed: json!(embed),
+            components: json!(components),
+        }
+    }
+
+    /// The component with the given id.
+    pub fn component(&self, custom_id: &str) -> Option<&Value> {
+        self.components
+            .as_array()?
+            .iter()
+            .filter_map(|row| row["components"].as_array())
+            .flatten()
+            .find(|component| component["custom_id"] == custom_id)
+    }
+
+    /// Whether the component with the given id is disabled.
+    ///
+    /// # Panics
+    /// Panics if there is no component with the given id.
+    pub fn is_disabled(&self, custom_id: &str) -> bool {
+        let component = self
+            .component(custom_id)
+            .unwrap_or_else(|| panic!("No component with the id {custom_id}"));
+
+        component["disabled"] == true
+    }
+
+    /// Applies the embeds and components of an update, keeping what it doesn't contain.
+    fn update(&mut self, data: &Value) {
+        if let Some(embed) = data["embeds"].get(0) {
+            self.embed = embed.clone();
+        }
+
+        if !data["components"].is_null() {
+            self.components = data["components"].clone();
+        }
+    }
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        let (presses, press_receiver) = mpsc::unbounded_channel();
+        let (modals, modal_receiver) = mpsc::unbounded_channel();
+        let (requests, request_receiver) = mpsc::unbounded_channel();
+
+        Self {
+            shared: Arc::new(Shared {
+                presses,
+                press_receiver: std::sync::Mutex::new(Some(press_receiver)),
+                modals,
+                modal_receiver: Mutex::new(modal_receiver),
+                requests,
+                request_receiver: Mutex::new(request_receiver),
+                message: std::sync::Mutex::new(None),
+                next_interaction_id: AtomicU64::new(1),
+            }),
+        }
+    }
+
+    /// Presses the button with the given id as the owner of the session.
+    ///
+    /// Fails if the interaction can't be built with the serenity version in use.
+    pub fn press(&self, custom_id: impl Into<String>) -> Result<(), Error> {
+        let id = self
+            .shared
+            .next_interaction_id
+            .fetch_add(1, Ordering::Relaxed);
+
+        self.shared
+            .presses
+            .send(interaction(id, &custom_id.into())?)
+            .ok();
+
+        Ok(())
+    }
+
+    /// Submits the next modal the session responds with, with the given values of its inputs.
+    pub fn submit_modal<I: Into<String>>(&self, inputs: impl IntoIterator<Item = I>) {
+        let inputs = inputs.into_iter().map(Into::into).collect();
+        self.shared.modals.send(Some(inputs)).ok();
+    }
+
+    /// Lets the next modal the session responds with time out without being submitted.
+    pub fn dismiss_modal(&self) {
+        self.shared.modals.send(None).ok();
+    }
+
+    /// Waits for the next request of the session, in the order they were sent.
+    pub async fn next_request(&self) -> MockRequest {
+        self.shared
+            .request_receiver
+            .lock()
+            .await
+            .recv()
+            .await
+            .expect("The transport holds a sender")
+    }
+
+    /// The next request of the session, if it already sent one that wasn't received yet.
+    pub fn try_next_request(&self) -> Option<MockRequest> {
+        self.shared
+            .request_receiver
+            .try_lock()
+            .ok()?
+            .try_recv()
+            .ok()
+    }
+
+    /// The message as the user currently sees it, or `None` if it wasn't sent yet.
+    pub fn message(&self) -> Option<MockMessage> {
+        self.shared
+            .message
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn record(&self, request: MockRequest) {
+        let mut message = self
+            .shared
+            .message
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match &request {
+            MockRequest::Send(sent) | MockRequest::Edit(sent) => *message = Some(sent.clone()),
+            // Only updates of the message change it, not acknowledgements or new messages.
+            MockRequest::Respond { response, .. } if response["type"] == 7 => {
+                if let Some(message) = message.as_mut() {
+                    message.update(&response["data"]);
+                }
+            }
+            MockRequest::EditResponse { response, .. } => {
+                if let Some(message) = message.as_mut() {
+                    message.update(response);
+                }
+            }
+            _ => {}
+        }
+
+        self.shared.requests.send(request).ok();
+    }
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for MockTransport {
+    fn session_id(&self) -> u64 {
+        1
+    }
+
+    fn owner(&self) -> UserId {
+        UserId::new(1)
+    }
+
+    fn channel_id(&self) -> ChannelId {
+        ChannelId::new(1)
+    }
+
+    fn send(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<MessageId, Error>> {
+        self.record(MockRequest::Send(MockMessage::new(&embed, &components)));
+        Box::pin(async { Ok(MessageId::new(MESSAGE_ID)) })
+    }
+
+    fn edit(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        self.record(MockRequest::Edit(MockMessage::new(&embed, &components)));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn presses(&self) -> BoxStream<'static, ComponentInteraction> {
+        let receiver = self
+            .shared
+            .press_receiver
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+
+        match receiver {
+            Some(receiver) => stream::unfold(receiver, |mut receiver| async move {
+                let press = receiver.recv().await?;
+                Some((press, receiver))
+            })
+            .boxed(),
+            None => stream::empty().boxed(),
+        }
+    }
+
+    fn interactions(&self) -> Arc<dyn Interactions> {
+        Arc::new(self.clone())
+    }
+}
+
+impl Interactions for MockTransport {
+    fn respond<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: CreateInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.record(MockRequest::Respond {
+            custom_id: interaction.data.custom_id.clone(),
+            response: json!(response),
+        });
+        Box::pin(async { Ok(()) })
+    }
+
+    fn edit_response<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: EditInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.record(MockRequest::EditResponse {
+            custom_id: interaction.data.custom_id.clone(),
+            response: json!(response),
+        });
+        Box::pin(async { Ok(()) })
+    }
+
+    fn followup<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        followup: CreateInteractionResponseFollowup,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.record(MockRequest::Followup {
+            custom_id: interaction.data.custom_id.clone(),
+            followup: json!(followup),
+        });
+        Box::pin(async { Ok(()) })
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        _interaction: &'a ComponentInteraction,
+        message: EditMessage,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        let mut edited = self.message().unwrap_or_default();
+        edited.update(&json!(message));
+
+        self.record(MockRequest::Edit(edited));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn quick_modal<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        _modal: CreateQuickModal,
+    ) -> BoxFuture<'a, Result<Option<Vec<String>>, Error>> {
+        self.record(MockRequest::Modal {
+            custom_id: interaction.data.custom_id.clone(),
+        });
+
+        Box::pin(async {
+            let inputs = self.shared.modal_receiver.lock().await.recv().await;
+            Ok(inputs.flatten())
+        })
+    }
+}
+
+/// A button press of the owner on the message of the session.
+///
+/// Serenity only builds interactions by deserializing them, so this lists every field a serenity 0.12 release requires.
+/// Fields the version in use doesn't know are ignored.
+pub(crate) fn interaction(id: u64, custom_id: &str) -> Result<ComponentInteraction, Error> {
+    let user = json!({
+        "id": "1",
+        "username": "owner",
+        "discriminator": "0",
+        "avatar": null,
+    });
+
+    serde_json::from_value(json!({
+        "id": id.to_string(),
+        "application_id": "1",
+        "type": 3,
+        "token": format!("token-{id}"),
+        "version": 1,
+        "channel_id": "1",
+        "locale": "en-US",
+        "entitlements": [],
+        "attachment_size_limit": 10 * 1024 * 1024,
+        "user": user,
+        "data": {
+            "custom_id": custom_id,
+            "component_type": 2,
+        },
+        "message": {
+            "id": MESSAGE_ID.to_string(),
+            "channel_id": "1",
+            "author": user,
+            "content": "",
+            "timestamp": "2025-01-01T00:00:00Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        },
+    }))
+    .map_err(|e| Error::from(format!("The mock interaction can't be built: {e}")))
+}
@@ -0,0 +1,154 @@
+pub(crate) mod discord;
+pub(crate) mod http;
+pub(crate) mod mock;
+pub(crate) mod resumed;
+
+use {
+    crate::Error,
+    poise::serenity_prelude::{
+        self, ChannelId, ComponentInteraction, CreateActionRow, CreateEmbed,
+        CreateInteractionResponse, CreateInteractionResponseFollowup, CreateQuickModal,
+        EditInteractionResponse, EditMessage, MessageId, UserId,
+        futures::{future::BoxFuture, stream::BoxStream},
+    },
+    std::sync::Arc,
+    tokio::time::Instant,
+};
+
+/// The connection of a paginator session to Discord: the message it shows and the button presses on it.
+///
+/// Paginators started from a command use a transport talking to Discord through serenity.
+/// Other transports, like the [MockTransport](crate::MockTransport), can be run through [paginate_on](crate::paginate_on).
+pub trait Transport: Send + Sync {
+    /// The id of the session, which the component ids start with.
+    fn session_id(&self) -> u64;
+
+    /// The user who is allowed to press the buttons.
+    fn owner(&self) -> UserId;
+
+    /// The channel the message is sent to.
+    fn channel_id(&self) -> ChannelId;
+
+    /// Sends the first page of the session, returning the id of the sent message.
+    fn send(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<MessageId, Error>>;
+
+    /// Edits the message of the session without responding to an interaction.
+    fn edit(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<(), Error>>;
+
+    /// The button presses of the owner on the components of the session. The session stops receiving them after its timeout.
+    fn presses(&self) -> BoxStream<'static, ComponentInteraction>;
+
+    /// Responds to the interactions of the session.
+    fn interactions(&self) -> Arc<dyn Interactions>;
+
+    /// The point in time at which the message can no longer be edited, if there is one.
+    fn ephemeral_deadline(&self) -> Option<Instant> {
+        None
+    }
+}
+
+/// Responds to button presses, on behalf of the session and its [View](crate::View).
+///
+/// Implemented for the serenity `Context` and `Http`, which respond through Discord.
+pub trait Interactions: Send + Sync + 'static {
+    /// Responds to the interaction.
+    fn respond<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: CreateInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Edits the response to an interaction that was acknowledged before.
+    fn edit_response<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: EditInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Sends a followup message to the interaction.
+    fn followup<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        followup: CreateInteractionResponseFollowup,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Edits the message the pressed component is on through its channel, without responding to the interaction.
+    ///
+    /// Used once the interaction was responded to otherwise, e.g. with a modal.
+    fn edit_message<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        message: EditMessage,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Responds to the interaction with the modal and waits for it to be submitted, acknowledging the submission.
+    ///
+    /// Returns the values of the inputs, or `None` if the modal wasn't submitted in time.
+    fn quick_modal<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        modal: CreateQuickModal,
+    ) -> BoxFuture<'a, Result<Option<Vec<String>>, Error>>;
+}
+
+impl Interactions for serenity_prelude::Context {
+    fn respond<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: CreateInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.http.respond(interaction, response)
+    }
+
+    fn edit_response<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        response: EditInteractionResponse,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.http.edit_response(interaction, response)
+    }
+
+    fn followup<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        followup: CreateInteractionResponseFollowup,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        self.http.followup(interaction, followup)
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        message: EditMessage,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Interactions::edit_message(self.http.as_ref(), interaction, message)
+    }
+
+    fn quick_modal<'a>(
+        &'a self,
+        interaction: &'a ComponentInteraction,
+        modal: CreateQuickModal,
+    ) -> BoxFuture<'a, Result<Option<Vec<String>>, Error>> {
+        Box::pin(async move {
+            let Some(response) = interaction.quick_modal(self, modal).await? else {
+                return Ok(None);
+            };
+
+            response
+                .interaction
+                .create_response(self, CreateInteractionResponse::Acknowledge)
+                .await
+                .ok();
+
+            Ok(Some(response.inputs))
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use {
+    super::{Interactions, Transport},
+    crate::{Error, dispatcher::InteractionDispatcher, store::SessionRecord},
+    poise::serenity_prelude::{
+        ChannelId, ComponentInteraction, CreateActionRow, CreateEmbed, EditMessage, Http,
+        MessageId, UserId,
+        futures::{
+            StreamExt,
+            future::BoxFuture,
+            stream::{self, BoxStream},
+        },
+    },
+    std::sync::Arc,
+};
+
+/// The transport of a session resumed from its [SessionRecord], e.g. after a restart.
+///
+/// The message already exists, so the first page is edited into it through its channel, like every later page that isn't
+/// a response to a button press. The button presses are received through the [InteractionDispatcher].
+pub(crate) struct ResumedTransport {
+    http: Arc<Http>,
+    record: SessionRecord,
+    dispatcher: InteractionDispatcher,
+}
+
+impl ResumedTransport {
+    pub(crate) fn new(
+        http: Arc<Http>,
+        record: SessionRecord,
+        dispatcher: InteractionDispatcher,
+    ) -> Self {
+        Self {
+            http,
+            record,
+            dispatcher,
+        }
+    }
+}
+
+impl Transport for ResumedTransport {
+    fn session_id(&self) -> u64 {
+        self.record.session_id
+    }
+
+    fn owner(&self) -> UserId {
+        self.record.owner
+    }
+
+    fn channel_id(&self) -> ChannelId {
+        self.record.channel_id
+    }
+
+    fn send(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<MessageId, Error>> {
+        Box::pin(async move {
+            self.edit(embed, components).await?;
+            Ok(self.record.message_id)
+        })
+    }
+
+    fn edit(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.record
+                .channel_id
+                .edit_message(
+                    &self.http,
+                    self.record.message_id,
+                    EditMessage::new().embed(embed).components(components),
+                )
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn presses(&self) -> BoxStream<'static, ComponentInteraction> {
+        let subscription =
+            self.dispatcher
+                .subscribe(self.session_id(), self.owner(), self.channel_id());
+
+        stream::unfold(subscription, |mut subscription| async move {
+            let press = subscription.next().await?;
+            Some((press, subscription))
+        })
+        .boxed()
+    }
+
+    fn interactions(&self) -> Arc<dyn Interactions> {
+        Arc::clone(&self.http) as Arc<dyn Interactions>
+    }
+}
@@ -1,48 +1,57 @@
 use {
     super::View,
-    crate::{Error, event::Event},
+    crate::{Error, event::Event, navigator::Navigator, transport::Interactions},
     poise::{
         ApplicationContext,
         serenity_prelude::{
-            self, ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton,
-            CreateInteractionResponse, CreateQuickModal, ReactionType,
+            ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateQuickModal,
+            ReactionType,
         },
     },
-    std::{sync::Arc, time::Duration},
-    tokio::sync::mpsc::UnboundedSender,
+    std::{fmt::Display, sync::Arc, time::Duration},
+    tokio::sync::mpsc::Sender,
 };
 
-pub struct DefaultView;
-
-impl<D> View<D, Error> for DefaultView
-where
-    D: 'static,
-{
-    fn create_ids(ctx: ApplicationContext<'_, D, Error>) -> Arc<[String]> {
+/// The view used by [paginate](crate::paginate).
+///
+/// Set `REFRESH` to `true` (or use [DefaultViewWithRefresh]) to add a "Refresh" button,
+/// which re-runs the generator for the current page. [paginate](crate::paginate) adds it with
+/// [PaginatorOptions::refresh_button](crate::PaginatorOptions::refresh_button).
+pub struct DefaultView<const REFRESH: bool = false>;
+
+/// A [DefaultView] with a "Refresh" button.
+pub type DefaultViewWithRefresh = DefaultView<true>;
+
+impl<const REFRESH: bool> DefaultView<REFRESH> {
+    /// Creates the component ids for a session, prefixed with the given session id.
+    ///
+    /// This is what [View::create_ids] creates, without needing a command context, e.g. for [check_view](crate::check_view).
+    pub fn ids(id: impl Display) -> Arc<[String]> {
         [
-            format!("{}_fast_rewind", ctx.id()),
-            format!("{}_rewind", ctx.id()),
-            format!("{}_counter", ctx.id()),
-            format!("{}_forward", ctx.id()),
-            format!("{}_fast_forward", ctx.id()),
-            format!("{}_jump_to", ctx.id()),
-            format!("{}_cancel", ctx.id()),
+            format!("{id}_fast_rewind"),
+            format!("{id}_rewind"),
+            format!("{id}_counter"),
+            format!("{id}_forward"),
+            format!("{id}_fast_forward"),
+            format!("{id}_jump_to"),
+            format!("{id}_cancel"),
+            format!("{id}_refresh"),
         ]
         .into()
     }
 
-    fn rerender_components(
-        ids: std::sync::Arc<[String]>,
+    /// Creates the components, with the given label on the page counter.
+    fn components(
+        ids: Arc<[String]>,
         current_idx: usize,
         length: usize,
         disable_all: bool,
+        counter: String,
     ) -> Vec<CreateActionRow> {
-        let (left_disabled, right_disabled) = match (disable_all, current_idx, length) {
-            (true, ..) => (true, true),
-            (false, 0, _) => (true, false),
-            (false, idx, len) if idx == len - 1 => (false, true),
-            (false, ..) => (false, false),
-        };
+        let navigator = Navigator::new(length).with_index(current_idx);
+
+        let left_disabled = disable_all || !navigator.has_previous();
+        let right_disabled = disable_all || !navigator.has_next();
 
         vec![
             CreateActionRow::Buttons(vec![
@@ -54,9 +63,7 @@ where
                     .emoji(ReactionType::Unicode("◀️".to_owned()))
                     .style(ButtonStyle::Secondary)
                     .disabled(left_disabled),
-                CreateButton::new(&ids[2])
-                    .label(format!("{} / {}", current_idx + 1, length))
-                    .disabled(true),
+                CreateButton::new(&ids[2]).label(counter).disabled(true),
                 CreateButton::new(&ids[3])
                     .emoji(ReactionType::Unicode("▶️".to_owned()))
                     .style(ButtonStyle::Secondary)
@@ -66,44 +73,99 @@ where
                     .style(ButtonStyle::Success)
                     .disabled(right_disabled),
             ]),
-            CreateActionRow::Buttons(vec![
-                CreateButton::new(&ids[5])
-                    .style(ButtonStyle::Primary)
-                    .label("Jump to page")
-                    .disabled(disable_all),
-                CreateButton::new(&ids[6])
-                    .style(ButtonStyle::Danger)
-                    .label("Cancel")
-                    .disabled(disable_all),
-            ]),
+            CreateActionRow::Buttons(
+                [
+                    Some(
+                        CreateButton::new(&ids[5])
+                            .style(ButtonStyle::Primary)
+                            .label("Jump to page")
+                            .disabled(disable_all),
+                    ),
+                    REFRESH.then(|| {
+                        CreateButton::new(&ids[7])
+                            .emoji(ReactionType::Unicode("🔄".to_owned()))
+                            .style(ButtonStyle::Secondary)
+                            .label("Refresh")
+                            .disabled(disable_all)
+                    }),
+                    Some(
+                        CreateButton::new(&ids[6])
+                            .style(ButtonStyle::Danger)
+                            .label("Cancel")
+                            .disabled(disable_all),
+                    ),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ),
         ]
     }
+}
+
+impl<D, const REFRESH: bool> View<D, Error> for DefaultView<REFRESH>
+where
+    D: 'static,
+{
+    fn create_ids(ctx: ApplicationContext<'_, D, Error>) -> Arc<[String]> {
+        Self::ids(ctx.id())
+    }
+
+    fn rerender_components(
+        ids: Arc<[String]>,
+        current_idx: usize,
+        length: usize,
+        disable_all: bool,
+    ) -> Vec<CreateActionRow> {
+        Self::components(
+            ids,
+            current_idx,
+            length,
+            disable_all,
+            format!("{} / {}", current_idx + 1, length),
+        )
+    }
+
+    fn loading_components(
+        ids: Arc<[String]>,
+        current_idx: usize,
+        length: usize,
+    ) -> Vec<CreateActionRow> {
+        Self::components(
+            ids,
+            current_idx,
+            length,
+            true,
+            format!("⏳ {} / {}", current_idx + 1, length),
+        )
+    }
 
     async fn on_button_press(
-        ctx: serenity_prelude::Context,
+        interactions: Arc<dyn Interactions>,
         press: ComponentInteraction,
-        tx: UnboundedSender<Event<Error>>,
+        tx: Sender<Event<Error>>,
         ids: Arc<[String]>,
     ) -> Result<(), Error> {
         match press.data.custom_id.as_str() {
             id if id == ids[0] => {
                 // Fast rewind
-                tx.send(Event::ToStart(press)).unwrap_or_default();
+                tx.send(Event::ToStart(press)).await.unwrap_or_default();
             }
             id if id == ids[1] => {
                 // Rewind
-                tx.send(Event::Previous(press)).unwrap_or_default();
+                tx.send(Event::Previous(press)).await.unwrap_or_default();
             }
             id if id == ids[3] => {
                 // Forward
-                tx.send(Event::Next(press)).unwrap_or_default();
+                tx.send(Event::Next(press)).await.unwrap_or_default();
             }
             id if id == ids[4] => {
                 // Fast forward
-                tx.send(Event::ToEnd(press)).unwrap_or_default();
+                tx.send(Event::ToEnd(press)).await.unwrap_or_default();
             }
             id if id == ids[5] => {
                 // Jump to page
+                // The session keeps one modal open, pressing again closes the modal opened before.
 
                 let modal = CreateQuickModal::new("Jump to Page")
                     .timeout(Duration::from_secs(30))
@@ -112,39 +174,42 @@ where
                 let tx = tx.clone();
 
                 tokio::spawn(async move {
-                    let response = press.quick_modal(&ctx, modal).await;
+                    let response = interactions.quick_modal(&press, modal).await;
 
                     let event = match response {
-                        Ok(Some(response)) => {
-                            let number = response.inputs[0].parse::<usize>();
-
-                            let event = match number {
-                                Ok(num) => Event::Jump(press, num - 1),
-                                Err(e) => Event::Error(press, Error::from(e)),
-                            };
-
-                            response
-                                .interaction
-                                .create_response(ctx, CreateInteractionResponse::Acknowledge)
-                                .await
-                                .ok();
-
-                            event
-                        }
+                        Ok(Some(inputs)) => match inputs[0]
+                            .parse::<usize>()
+                            .map(|num| num.checked_sub(1))
+                        {
+                            Ok(Some(page)) => Event::Jump(press, page),
+                            Ok(None) => Event::Error(press, Error::from("Page 0 does not exist.")),
+                            Err(e) => Event::Error(press, Error::from(e)),
+                        },
                         Ok(None) => return,
-                        Err(e) => Event::Error(press, e.into()),
+                        Err(e) => Event::Error(press, e),
                     };
 
-                    tx.send(event).unwrap_or_default();
+                    tx.send(event).await.unwrap_or_default();
                 });
             }
 
             id if id == ids[6] => {
                 // Cancel
-                tx.send(Event::CancelledByUser(press)).unwrap_or_default();
+                tx.send(Event::CancelledByUser(press))
+                    .await
+                    .unwrap_or_default();
+            }
+
+            id if id == ids[7] => {
+                // Refresh
+                tx.send(Event::Refresh(press)).await.unwrap_or_default();
             }
 
-            _ => unreachable!("Unexpected button ID: {}", press.data.custom_id),
+            // The ids come from Discord, so a forged or outdated press shouldn't end the session.
+            _ => {
+                let e = Error::from(format!("Unknown button {}", press.data.custom_id));
+                tx.send(Event::Error(press, e)).await.unwrap_or_default();
+            }
         }
 
         Ok(())
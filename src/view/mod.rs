@@ -1,16 +1,16 @@
 pub(crate) mod default_view;
 
 use {
-    crate::{Error, event::Event},
+    crate::{Error, event::Event, transport::Interactions},
     poise::{
         ApplicationContext,
-        serenity_prelude::{self, ComponentInteraction, CreateActionRow},
+        serenity_prelude::{ComponentInteraction, CreateActionRow},
     },
     std::sync::Arc,
-    tokio::sync::mpsc::UnboundedSender,
+    tokio::sync::mpsc::Sender,
 };
 
-pub trait View<D, E>: Send + Sync + 'static {
+pub trait View<D, E, C = ()>: Send + Sync + 'static {
     fn create_ids(ctx: ApplicationContext<'_, D, E>) -> Arc<[String]>;
 
     fn rerender_components(
@@ -20,10 +20,23 @@ pub trait View<D, E>: Send + Sync + 'static {
         disable_all: bool,
     ) -> Vec<CreateActionRow>;
 
+    /// The components shown while a slow page is being rendered, if the [loading indicator](crate::PaginatorOptions::loading_indicator) is enabled.
+    /// `current_idx` is the index of the page being rendered. All buttons should be disabled.
+    fn loading_components(
+        ids: Arc<[String]>,
+        current_idx: usize,
+        length: usize,
+    ) -> Vec<CreateActionRow> {
+        Self::rerender_components(ids, current_idx, length, true)
+    }
+
+    /// Handles a button press on one of the components, sending the resulting [Event] to the session.
+    ///
+    /// Responses that don't go through the session, like modals, are sent through `interactions`.
     fn on_button_press(
-        ctx: serenity_prelude::Context,
+        interactions: Arc<dyn Interactions>,
         press: ComponentInteraction,
-        tx: UnboundedSender<Event<E>>,
+        tx: Sender<Event<E, C>>,
         ids: Arc<[String]>,
     ) -> impl Future<Output = Result<(), Error>> + Send + 'static;
 }
@@ -0,0 +1,66 @@
+//! Starting paginator sessions with the [DefaultView](poise_paginator::DefaultView) on a transport.
+//!
+//! Shared by the tests driving sessions through the mock transport and the stand-in Discord server.
+
+#![allow(dead_code)]
+
+use {
+    poise::serenity_prelude::CreateEmbed,
+    poise_paginator::{
+        CancellationType, MockTransport, PaginatorOptions, Session, Transport, paginate_on,
+    },
+    tokio::task::JoinHandle,
+};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+pub type Outcome = Result<CancellationType, Error>;
+
+/// Spawns a session with `length` pages on the transport, rendering the page at an index with `page`.
+pub fn spawn<T, Page, Fut>(
+    transport: T,
+    length: usize,
+    options: PaginatorOptions<()>,
+    page: Page,
+) -> JoinHandle<Outcome>
+where
+    T: Transport + 'static,
+    Page: Fn(usize, CancellationType) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<CreateEmbed, Error>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        paginate_on(
+            (),
+            &transport,
+            move |_, index, cancellation_type, _session: Session<()>| {
+                page(index, cancellation_type)
+            },
+            length,
+            options,
+            (),
+        )
+        .await
+    })
+}
+
+/// A page titled with its number, describing how the session was cancelled.
+pub async fn numbered(
+    index: usize,
+    cancellation_type: CancellationType,
+) -> Result<CreateEmbed, Error> {
+    Ok(CreateEmbed::new()
+        .title(format!("Page {}", index + 1))
+        .description(format!("{cancellation_type:?}")))
+}
+
+/// The label of the page counter of the [DefaultView](poise_paginator::DefaultView) on the message of the mock transport.
+pub fn counter(transport: &MockTransport) -> String {
+    transport
+        .message()
+        .expect("The message was sent")
+        .component("1_counter")
+        .expect("The message has a counter")["label"]
+        .as_str()
+        .expect("The counter has a label")
+        .to_owned()
+}
@@ -0,0 +1,113 @@
+use {
+    poise::{
+        ApplicationContext,
+        serenity_prelude::{ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton},
+    },
+    poise_paginator::{DefaultView, DefaultViewWithRefresh, Event, Interactions, View, check_view},
+    std::sync::Arc,
+    tokio::sync::mpsc::Sender,
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[tokio::test]
+async fn the_default_view_conforms() {
+    check_view::<DefaultView, (), Error, ()>(<DefaultView>::ids)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn the_default_view_with_refresh_conforms() {
+    check_view::<DefaultViewWithRefresh, (), Error, ()>(DefaultViewWithRefresh::ids)
+        .await
+        .unwrap();
+}
+
+/// A view with a button per page, which never disables its "Next" button and doesn't handle its "Last" button.
+struct BrokenView;
+
+impl BrokenView {
+    fn ids(id: u64) -> Arc<[String]> {
+        [
+            format!("{id}_next"),
+            format!("{id}_last"),
+            "page".to_owned(),
+        ]
+        .into()
+    }
+}
+
+impl View<(), Error> for BrokenView {
+    fn create_ids(ctx: ApplicationContext<'_, (), Error>) -> Arc<[String]> {
+        Self::ids(ctx.id())
+    }
+
+    fn rerender_components(
+        ids: Arc<[String]>,
+        _current_idx: usize,
+        length: usize,
+        disable_all: bool,
+    ) -> Vec<CreateActionRow> {
+        let pages = (0..length.min(6))
+            .map(|page| {
+                CreateButton::new(format!("{}_{page}", ids[2]))
+                    .label(format!("Page {}", page + 1))
+                    .style(ButtonStyle::Secondary)
+                    .disabled(true)
+            })
+            .collect();
+
+        vec![
+            CreateActionRow::Buttons(vec![
+                CreateButton::new(&ids[0])
+                    .label("Next")
+                    .disabled(disable_all),
+                CreateButton::new(&ids[1])
+                    .label("Last")
+                    .disabled(disable_all),
+            ]),
+            CreateActionRow::Buttons(pages),
+        ]
+    }
+
+    async fn on_button_press(
+        _interactions: Arc<dyn Interactions>,
+        press: ComponentInteraction,
+        tx: Sender<Event<Error>>,
+        ids: Arc<[String]>,
+    ) -> Result<(), Error> {
+        match press.data.custom_id.as_str() {
+            id if id == ids[0] => {
+                tx.send(Event::Next(press)).await.unwrap_or_default();
+            }
+            _ => unreachable!("Unexpected button ID: {}", press.data.custom_id),
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn reports_every_violation() {
+    let error = check_view::<BrokenView, (), Error, ()>(BrokenView::ids)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    for violation in [
+        "the id page doesn't start with the session id 1234567890",
+        "row 2 on page 1 of 1000 has 6 components, at most 5 are allowed",
+        "the id page_0 on page 1 of 1 doesn't start with the session id 1234567890",
+        "1234567890_next is enabled on page 1 of 1 but goes forward",
+        "1234567890_next is enabled on page 2 of 2 but goes forward",
+        "pressing 1234567890_last on page 1 of 2 panics",
+    ] {
+        assert!(
+            error.contains(violation),
+            "{violation:?} is missing from:\n{error}"
+        );
+    }
+
+    assert!(!error.contains("page 1 of 2 but goes forward"));
+}
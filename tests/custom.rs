@@ -0,0 +1,262 @@
+use {
+    poise::{
+        ApplicationContext,
+        serenity_prelude::{
+            ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed,
+        },
+    },
+    poise_paginator::{
+        CancellationType, CustomAction, Event, Interactions, MockRequest, MockTransport,
+        PaginationInfo, PaginatorOptions, Session, View, custom_paginate_on,
+    },
+    std::{sync::Arc, time::Duration},
+    tokio::{sync::mpsc::Sender, task::JoinHandle},
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The buttons of the [ButtonView], each sending the custom event of the same name.
+const BUTTONS: [&str; 7] = ["none", "rerender", "go_to", "end", "fail", "slow", "broken"];
+
+/// The custom events of the [ButtonView], handled by [handle].
+#[derive(Debug)]
+enum Press {
+    None,
+    Rerender,
+    GoTo,
+    End,
+    Fail,
+    Slow,
+}
+
+/// A view with a button per custom event, and a broken button the view fails to handle.
+struct ButtonView;
+
+impl ButtonView {
+    fn ids(id: u64) -> Arc<[String]> {
+        BUTTONS
+            .iter()
+            .map(|button| format!("{id}_{button}"))
+            .collect()
+    }
+}
+
+impl View<(), Error, Press> for ButtonView {
+    fn create_ids(ctx: ApplicationContext<'_, (), Error>) -> Arc<[String]> {
+        Self::ids(ctx.id())
+    }
+
+    fn rerender_components(
+        ids: Arc<[String]>,
+        _current_idx: usize,
+        _length: usize,
+        disable_all: bool,
+    ) -> Vec<CreateActionRow> {
+        ids.chunks(5)
+            .map(|row| {
+                let buttons = row
+                    .iter()
+                    .map(|id| {
+                        CreateButton::new(id)
+                            .style(ButtonStyle::Secondary)
+                            .disabled(disable_all)
+                    })
+                    .collect();
+
+                CreateActionRow::Buttons(buttons)
+            })
+            .collect()
+    }
+
+    async fn on_button_press(
+        _interactions: Arc<dyn Interactions>,
+        press: ComponentInteraction,
+        tx: Sender<Event<Error, Press>>,
+        ids: Arc<[String]>,
+    ) -> Result<(), Error> {
+        let custom = match press.data.custom_id.as_str() {
+            id if id == ids[0] => Press::None,
+            id if id == ids[1] => Press::Rerender,
+            id if id == ids[2] => Press::GoTo,
+            id if id == ids[3] => Press::End,
+            id if id == ids[4] => Press::Fail,
+            id if id == ids[5] => Press::Slow,
+            _ => return Err(Error::from("The broken button can't be handled")),
+        };
+
+        tx.send(Event::Custom(press, custom))
+            .await
+            .unwrap_or_default();
+
+        Ok(())
+    }
+}
+
+struct Info;
+
+impl PaginationInfo for Info {
+    type PoiseData = ();
+    type PoiseError = Error;
+    type CustomEvent = Press;
+    type View = ButtonView;
+}
+
+async fn handle(press: Press) -> Result<CustomAction, Error> {
+    match press {
+        Press::None => Ok(CustomAction::None),
+        Press::Rerender => Ok(CustomAction::Rerender),
+        Press::GoTo => Ok(CustomAction::GoTo(2)),
+        Press::End => Ok(CustomAction::End),
+        Press::Fail => Err(Error::from("The handler failed")),
+        Press::Slow => {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(CustomAction::Rerender)
+        }
+    }
+}
+
+/// Starts a session with three pages and the [ButtonView] on a mock transport.
+///
+/// The pages count how often they were rendered in the session state.
+fn start(
+    options: PaginatorOptions<usize>,
+) -> (MockTransport, JoinHandle<Result<CancellationType, Error>>) {
+    let transport = MockTransport::new();
+
+    let session = tokio::spawn({
+        let transport = transport.clone();
+
+        async move {
+            custom_paginate_on::<Info, _, _, _, _, _>(
+                (),
+                &transport,
+                ButtonView::ids(1),
+                |_, index, cancellation_type, session: Session<usize>| async move {
+                    let renders = {
+                        let mut renders = session.state().await;
+                        *renders += 1;
+                        *renders
+                    };
+
+                    Ok(CreateEmbed::new()
+                        .title(format!("Page {}", index + 1))
+                        .description(format!("Render {renders}, {cancellation_type:?}")))
+                },
+                |_, press, _, _| handle(press),
+                3,
+                options,
+                0,
+            )
+            .await
+        }
+    });
+
+    (transport, session)
+}
+
+#[tokio::test(start_paused = true)]
+async fn ends_with_the_error_of_the_view() {
+    let (transport, session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_broken").unwrap();
+
+    let error = session.await.unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "The broken button can't be handled");
+}
+
+#[tokio::test(start_paused = true)]
+async fn defers_slow_handlers() {
+    let (transport, _session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_slow").unwrap();
+
+    let request = transport.next_request().await;
+    assert!(request.is_acknowledgement());
+
+    let request = transport.next_request().await;
+    assert!(matches!(request, MockRequest::EditResponse { .. }));
+    assert_eq!(
+        request.response().unwrap()["embeds"][0]["description"],
+        "Render 2, NotCancelled"
+    );
+}
+
+fn description(transport: &MockTransport) -> String {
+    transport.message().expect("The message was sent").embed["description"]
+        .as_str()
+        .expect("The embed has a description")
+        .to_owned()
+}
+
+#[tokio::test(start_paused = true)]
+async fn acknowledges_events_without_an_action() {
+    let (transport, _session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_none").unwrap();
+
+    assert!(transport.next_request().await.is_acknowledgement());
+    assert_eq!(description(&transport), "Render 1, NotCancelled");
+}
+
+#[tokio::test(start_paused = true)]
+async fn rerenders_the_current_page() {
+    let (transport, _session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_rerender").unwrap();
+
+    let request = transport.next_request().await;
+    assert!(
+        matches!(&request, MockRequest::Respond { custom_id, .. } if custom_id == "1_rerender")
+    );
+    assert_eq!(request.response().unwrap()["type"], 7);
+    assert_eq!(transport.message().unwrap().embed["title"], "Page 1");
+    assert_eq!(description(&transport), "Render 2, NotCancelled");
+}
+
+#[tokio::test(start_paused = true)]
+async fn goes_to_the_page_of_the_handler() {
+    let (transport, _session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_go_to").unwrap();
+    transport.next_request().await;
+
+    assert_eq!(transport.message().unwrap().embed["title"], "Page 3");
+}
+
+#[tokio::test(start_paused = true)]
+async fn ends_the_session_through_the_handler() {
+    let (transport, session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_end").unwrap();
+    transport.next_request().await;
+
+    assert_eq!(description(&transport), "Render 2, UserInput");
+    assert!(transport.message().unwrap().is_disabled("1_none"));
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::UserInput);
+}
+
+#[tokio::test(start_paused = true)]
+async fn ends_with_the_error_of_the_handler() {
+    let (transport, session) = start(TIMEOUT.into());
+    transport.next_request().await;
+
+    transport.press("1_fail").unwrap();
+
+    let error = session.await.unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "The handler failed");
+
+    // The message is left in a finished state.
+    let MockRequest::Edit(message) = transport.next_request().await else {
+        panic!("The message is edited directly after an error");
+    };
+    assert_eq!(message.embed["description"], "Render 2, Error");
+    assert!(message.is_disabled("1_none"));
+}
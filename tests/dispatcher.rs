@@ -0,0 +1,78 @@
+use {
+    discord_stand_in::{CHANNEL_ID, Discord, OWNER_ID},
+    poise::serenity_prelude::{ChannelId, FullEvent, UserId},
+    poise_paginator::InteractionDispatcher,
+};
+
+#[tokio::test]
+async fn routes_presses_to_the_session_of_their_id() {
+    let discord = Discord::start().await;
+    let dispatcher = InteractionDispatcher::new();
+
+    let mut first = dispatcher.subscribe(1, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+    let mut second = dispatcher.subscribe(12, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+    assert_eq!(dispatcher.count(), 2);
+
+    for (custom_id, routed) in [
+        ("1_forward", true),
+        ("12_forward", true),
+        ("123_forward", false),
+    ] {
+        assert_eq!(
+            dispatcher.handle_event(&discord.press_event(custom_id, OWNER_ID)),
+            routed,
+            "{custom_id}"
+        );
+    }
+
+    assert_eq!(first.next().await.unwrap().data.custom_id, "1_forward");
+    assert_eq!(second.next().await.unwrap().data.custom_id, "12_forward");
+}
+
+#[tokio::test]
+async fn ignores_presses_of_other_users() {
+    let discord = Discord::start().await;
+    let dispatcher = InteractionDispatcher::new();
+
+    let _subscription = dispatcher.subscribe(1, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+
+    assert!(!dispatcher.handle_event(&discord.press_event("1_forward", OWNER_ID + 1)));
+}
+
+#[tokio::test]
+async fn ignores_other_components_and_events() {
+    let discord = Discord::start().await;
+    let dispatcher = InteractionDispatcher::new();
+
+    let _subscription = dispatcher.subscribe(1, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+
+    // Components of other features don't start with a session id.
+    assert!(!dispatcher.handle_event(&discord.press_event("forward_1", OWNER_ID)));
+    assert!(!dispatcher.handle_event(&FullEvent::ShardsReady { total_shards: 1 }));
+}
+
+#[tokio::test]
+async fn stops_routing_once_the_subscription_is_dropped() {
+    let discord = Discord::start().await;
+    let dispatcher = InteractionDispatcher::new();
+
+    let subscription = dispatcher.subscribe(1, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+    drop(subscription);
+
+    assert_eq!(dispatcher.count(), 0);
+    assert!(!dispatcher.handle_event(&discord.press_event("1_forward", OWNER_ID)));
+}
+
+#[tokio::test]
+async fn keeps_the_route_of_a_newer_subscription() {
+    let discord = Discord::start().await;
+    let dispatcher = InteractionDispatcher::new();
+
+    let older = dispatcher.subscribe(1, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+    let mut newer = dispatcher.subscribe(1, UserId::new(OWNER_ID), ChannelId::new(CHANNEL_ID));
+    drop(older);
+
+    assert_eq!(dispatcher.count(), 1);
+    assert!(dispatcher.handle_event(&discord.press_event("1_forward", OWNER_ID)));
+    assert_eq!(newer.next().await.unwrap().data.custom_id, "1_forward");
+}
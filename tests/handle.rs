@@ -0,0 +1,181 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    poise::serenity_prelude::CreateEmbed,
+    poise_paginator::{
+        CancellationType, MockRequest, MockTransport, NavigationEvent, PaginatorHandle,
+        PaginatorOptions, Session, paginate_on,
+    },
+    session::{Outcome, counter, numbered},
+    std::time::Duration,
+    tokio::task::JoinHandle,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Starts a session with three pages on a mock transport, controlled through the returned handle.
+fn start() -> (MockTransport, PaginatorHandle, JoinHandle<Outcome>) {
+    let transport = MockTransport::new();
+    let handle = PaginatorHandle::new();
+
+    let session = session::spawn(
+        transport.clone(),
+        3,
+        PaginatorOptions::new(TIMEOUT).handle(handle.clone()),
+        numbered,
+    );
+
+    (transport, handle, session)
+}
+
+#[tokio::test(start_paused = true)]
+async fn jumps_to_the_page() {
+    let (transport, handle, _session) = start();
+    transport.next_request().await;
+
+    handle.jump(1);
+
+    let MockRequest::Edit(message) = transport.next_request().await else {
+        panic!("The message is edited directly");
+    };
+    assert_eq!(message.embed["title"], "Page 2");
+    assert_eq!(counter(&transport), "2 / 3");
+
+    // Pages past the last one are clamped.
+    handle.jump(10);
+    transport.next_request().await;
+    assert_eq!(counter(&transport), "3 / 3");
+}
+
+#[tokio::test(start_paused = true)]
+async fn applies_commands_sent_before_the_session_started() {
+    let transport = MockTransport::new();
+    let handle = PaginatorHandle::new();
+    handle.jump(2);
+
+    let _session = session::spawn(
+        transport.clone(),
+        3,
+        PaginatorOptions::new(TIMEOUT).handle(handle),
+        numbered,
+    );
+
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Send(_)
+    ));
+    transport.next_request().await;
+    assert_eq!(counter(&transport), "3 / 3");
+}
+
+#[tokio::test(start_paused = true)]
+async fn closes_the_session() {
+    let (transport, handle, session) = start();
+    transport.next_request().await;
+
+    handle.close();
+
+    assert_eq!(
+        session.await.unwrap().unwrap(),
+        CancellationType::Programmatic
+    );
+
+    let MockRequest::Edit(message) = transport.next_request().await else {
+        panic!("The message is edited directly");
+    };
+    assert_eq!(message.embed["description"], "Programmatic");
+    assert!(message.is_disabled("1_forward"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn emits_navigation_events() {
+    let (transport, handle, session) = start();
+    let mut events = handle.subscribe();
+    transport.next_request().await;
+
+    transport.press("1_forward").unwrap();
+    assert_eq!(
+        events.recv().await.unwrap(),
+        NavigationEvent::Rendered {
+            index: 1,
+            length: 3
+        }
+    );
+
+    handle.set_length(5);
+    assert_eq!(
+        events.recv().await.unwrap(),
+        NavigationEvent::Rendered {
+            index: 1,
+            length: 5
+        }
+    );
+
+    handle.close();
+    assert_eq!(
+        events.recv().await.unwrap(),
+        NavigationEvent::Ended(CancellationType::Programmatic)
+    );
+
+    session.await.unwrap().unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn clamps_the_page_when_the_length_shrinks() {
+    let (transport, handle, _session) = start();
+    transport.next_request().await;
+
+    handle.jump(2);
+    transport.next_request().await;
+
+    handle.set_length(2);
+
+    let MockRequest::Edit(message) = transport.next_request().await else {
+        panic!("The message is edited directly");
+    };
+    assert_eq!(message.embed["title"], "Page 2");
+    assert!(message.is_disabled("1_forward"));
+    assert_eq!(counter(&transport), "2 / 2");
+}
+
+#[tokio::test(start_paused = true)]
+async fn picks_up_lengths_set_by_the_generator() {
+    let transport = MockTransport::new();
+
+    let _session = tokio::spawn({
+        let transport = transport.clone();
+
+        async move {
+            paginate_on(
+                (),
+                &transport,
+                |_, index, _, session: Session<()>| async move {
+                    // The last page finds out there are more.
+                    if index == session.length() - 1 {
+                        session.set_length(session.length() + 2);
+                    }
+
+                    Ok::<_, session::Error>(CreateEmbed::new().title(format!("Page {}", index + 1)))
+                },
+                2,
+                PaginatorOptions::new(TIMEOUT),
+                (),
+            )
+            .await
+        }
+    });
+
+    transport.next_request().await;
+    assert_eq!(counter(&transport), "1 / 2");
+
+    transport.press("1_forward").unwrap();
+    transport.next_request().await;
+
+    // The render in progress already shows the new length.
+    assert_eq!(counter(&transport), "2 / 4");
+    assert!(!transport.message().unwrap().is_disabled("1_forward"));
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(transport.try_next_request().is_none());
+}
@@ -0,0 +1,454 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    discord_stand_in::{Discord, OWNER_ID, Request},
+    poise_paginator::{
+        CancellationType, HttpTransport, PaginatorHandle, PaginatorOptions, RetryPolicy,
+        SessionRegistry, Transport,
+    },
+    serde_json::{Value, json},
+    session::{Outcome, numbered},
+    std::time::Duration,
+    tokio::task::JoinHandle,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A timeout the tests don't wait for, so that only the session ending can stop it.
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+/// Starts a session with three pages on the stand-in server, returning its session id.
+fn start(discord: &Discord, options: PaginatorOptions<()>) -> (u64, JoinHandle<Outcome>) {
+    let transport = HttpTransport::new(
+        discord.http.clone(),
+        discord.command(),
+        discord.dispatcher.clone(),
+    );
+
+    let id = transport.session_id();
+    let session = session::spawn(transport, 3, options, numbered);
+
+    (id, session)
+}
+
+/// Starts a session and skips the requests sending the first page.
+async fn started(discord: &Discord, options: PaginatorOptions<()>) -> (u64, JoinHandle<Outcome>) {
+    let started = start(discord, options);
+
+    discord.next_request().await;
+    discord.next_request().await;
+
+    started
+}
+
+fn embed(page: usize, cancellation_type: &str) -> Value {
+    json!({
+        "type": "rich",
+        "title": format!("Page {page}"),
+        "description": cancellation_type,
+    })
+}
+
+/// The components of the [DefaultView](poise_paginator::DefaultView), as Discord receives them.
+fn components(
+    id: u64,
+    counter: &str,
+    left_disabled: bool,
+    right_disabled: bool,
+    disable_all: bool,
+) -> Value {
+    let button = |name: &str, style: u8, disabled: bool| {
+        json!({
+            "type": 2,
+            "custom_id": format!("{id}_{name}"),
+            "style": style,
+            "disabled": disabled,
+        })
+    };
+
+    let with = |mut button: Value, key: &str, value: Value| {
+        button[key] = value;
+        button
+    };
+
+    json!([
+        {
+            "type": 1,
+            "components": [
+                with(button("fast_rewind", 3, left_disabled), "emoji", json!({ "name": "⏪" })),
+                with(button("rewind", 2, left_disabled), "emoji", json!({ "name": "◀️" })),
+                with(button("counter", 1, true), "label", json!(counter)),
+                with(button("forward", 2, right_disabled), "emoji", json!({ "name": "▶️" })),
+                with(button("fast_forward", 3, right_disabled), "emoji", json!({ "name": "⏩" })),
+            ],
+        },
+        {
+            "type": 1,
+            "components": [
+                with(button("jump_to", 1, disable_all), "label", json!("Jump to page")),
+                with(button("cancel", 4, disable_all), "label", json!("Cancel")),
+            ],
+        },
+    ])
+}
+
+#[tokio::test]
+async fn sends_the_first_page_as_the_command_response() {
+    let discord = Discord::start().await;
+    let (id, _session) = start(&discord, TIMEOUT.into());
+
+    let request = discord.next_request().await;
+    assert_eq!(
+        request,
+        Request {
+            method: "POST".to_owned(),
+            path: format!("/api/v10/interactions/{id}/command-token/callback"),
+            body: json!({
+                "type": 4,
+                "data": {
+                    "attachments": [],
+                    "embeds": [embed(1, "NotCancelled")],
+                    "components": components(id, "1 / 3", true, false, false),
+                },
+            }),
+        }
+    );
+
+    // The message is fetched to learn its id.
+    let request = discord.next_request().await;
+    assert_eq!(request.method, "GET");
+    assert_eq!(
+        request.path,
+        "/api/v10/webhooks/1/command-token/messages/@original"
+    );
+}
+
+#[tokio::test]
+async fn updates_the_message_through_the_press() {
+    let discord = Discord::start().await;
+    let (id, _session) = started(&discord, TIMEOUT.into()).await;
+
+    discord.press(&format!("{id}_fast_forward")).await;
+
+    assert_eq!(
+        discord.next_request().await,
+        Request {
+            method: "POST".to_owned(),
+            path: "/api/v10/interactions/1/press-token-1/callback".to_owned(),
+            body: json!({
+                "type": 7,
+                "data": {
+                    "attachments": [],
+                    "embeds": [embed(3, "NotCancelled")],
+                    "components": components(id, "3 / 3", false, true, false),
+                },
+            }),
+        }
+    );
+}
+
+#[tokio::test]
+async fn reports_errors_through_an_ephemeral_followup() {
+    let discord = Discord::start().await;
+    let (id, _session) = started(&discord, TIMEOUT.into()).await;
+
+    // Modals need a gateway connection, which the HTTP transport doesn't have.
+    discord.press(&format!("{id}_jump_to")).await;
+
+    let request = discord.next_request().await;
+    assert_eq!(
+        request.path,
+        "/api/v10/interactions/1/press-token-1/callback"
+    );
+    assert_eq!(request.body["type"], 6);
+
+    assert_eq!(
+        discord.next_request().await,
+        Request {
+            method: "POST".to_owned(),
+            path: "/api/v10/webhooks/1/press-token-1".to_owned(),
+            body: json!({
+                "attachments": [],
+                "embeds": [{
+                    "type": "rich",
+                    "title": "Error",
+                    "description": "Modals need a gateway connection",
+                    "color": 0xE74C3C,
+                }],
+                "flags": 64,
+            }),
+        }
+    );
+}
+
+#[tokio::test]
+async fn cancelling_disables_all_buttons() {
+    let discord = Discord::start().await;
+    let (id, session) = started(&discord, TIMEOUT.into()).await;
+
+    discord.press(&format!("{id}_forward")).await;
+    discord.next_request().await;
+
+    discord.press(&format!("{id}_cancel")).await;
+
+    let request = discord.next_request().await;
+    assert_eq!(
+        request.path,
+        "/api/v10/interactions/2/press-token-2/callback"
+    );
+    assert_eq!(
+        request.body["data"]["components"],
+        components(id, "2 / 3", true, true, true)
+    );
+    assert_eq!(request.body["data"]["embeds"][0], embed(2, "UserInput"));
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::UserInput);
+}
+
+#[tokio::test]
+async fn disables_all_buttons_after_the_timeout() {
+    let discord = Discord::start().await;
+    let (id, session) = started(&discord, Duration::from_millis(200).into()).await;
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Timeout);
+
+    assert_eq!(
+        discord.next_request().await,
+        Request {
+            method: "PATCH".to_owned(),
+            path: "/api/v10/webhooks/1/command-token/messages/@original".to_owned(),
+            body: json!({
+                "embeds": [embed(1, "Timeout")],
+                "components": components(id, "1 / 3", true, true, true),
+            }),
+        }
+    );
+}
+
+#[tokio::test]
+async fn ephemeral_sessions_end_before_the_token_expires() {
+    let discord = Discord::start().await;
+
+    // The token expires in 15 minutes, the session ends 30 seconds before that.
+    let command = discord.command_created(Duration::from_secs(14 * 60 + 28));
+    let transport = HttpTransport::new(discord.http.clone(), command, discord.dispatcher.clone())
+        .ephemeral(true);
+
+    let id = transport.session_id();
+    let session = session::spawn(transport, 3, PaginatorOptions::new(TIMEOUT), numbered);
+
+    let request = discord.next_request().await;
+    assert_eq!(request.body["type"], 4);
+    assert_eq!(request.body["data"]["flags"], 64);
+    discord.next_request().await;
+
+    assert_eq!(
+        session.await.unwrap().unwrap(),
+        CancellationType::TokenExpired
+    );
+
+    // Ephemeral messages can only be edited through the token.
+    assert_eq!(
+        discord.next_request().await,
+        Request {
+            method: "PATCH".to_owned(),
+            path: "/api/v10/webhooks/1/command-token/messages/@original".to_owned(),
+            body: json!({
+                "embeds": [embed(1, "TokenExpired")],
+                "components": components(id, "1 / 3", true, true, true),
+            }),
+        }
+    );
+}
+
+#[tokio::test]
+async fn ends_when_the_message_is_gone() {
+    for (status, code, cancellation_type) in [
+        (404, 10008, CancellationType::MessageDeleted),
+        (404, 10003, CancellationType::ChannelDeleted),
+        (403, 50001, CancellationType::MissingPermissions),
+        (403, 50013, CancellationType::MissingPermissions),
+    ] {
+        let discord = Discord::start().await;
+        let (_, session) = started(&discord, Duration::from_millis(200).into()).await;
+
+        // The final edit after the timeout fails.
+        discord.fail_next(status, code);
+
+        assert_eq!(session.await.unwrap().unwrap(), cancellation_type, "{code}");
+        assert_eq!(discord.next_request().await.method, "PATCH");
+    }
+}
+
+#[tokio::test]
+async fn renders_the_error_after_other_failures() {
+    let discord = Discord::start().await;
+    let (id, session) = started(
+        &discord,
+        PaginatorOptions::new(Duration::from_millis(200)).retry(RetryPolicy::none()),
+    )
+    .await;
+
+    discord.fail_next(400, 50035);
+
+    assert!(session.await.unwrap().is_err());
+
+    // The failed edit, then the attempt to leave the message in a finished state.
+    assert_eq!(discord.next_request().await.method, "PATCH");
+    assert_eq!(
+        discord.next_request().await.body,
+        json!({
+            "embeds": [embed(1, "Error")],
+            "components": components(id, "1 / 3", true, true, true),
+        })
+    );
+}
+
+#[tokio::test]
+async fn retries_transient_failures() {
+    let discord = Discord::start().await;
+    let (_, session) = started(
+        &discord,
+        PaginatorOptions::new(Duration::from_millis(200))
+            .retry(RetryPolicy::new(2).initial_backoff(Duration::from_millis(10))),
+    )
+    .await;
+
+    discord.fail_next(502, 0);
+    discord.fail_next(500, 0);
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Timeout);
+
+    for _ in 0..3 {
+        let request = discord.next_request().await;
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.body["embeds"][0], embed(1, "Timeout"));
+    }
+}
+
+#[tokio::test]
+async fn gives_up_once_the_retries_are_used_up() {
+    let discord = Discord::start().await;
+    let (_, session) = started(
+        &discord,
+        PaginatorOptions::new(Duration::from_millis(200))
+            .retry(RetryPolicy::new(1).initial_backoff(Duration::from_millis(10))),
+    )
+    .await;
+
+    discord.fail_next(503, 0);
+    discord.fail_next(503, 0);
+
+    assert!(session.await.unwrap().is_err());
+
+    // Two attempts at the final edit, then the attempt to render the error.
+    for description in ["Timeout", "Timeout", "Error"] {
+        let request = discord.next_request().await;
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.body["embeds"][0], embed(1, description));
+    }
+}
+
+#[tokio::test]
+async fn stops_routing_presses_once_closed() {
+    let discord = Discord::start().await;
+    let handle = PaginatorHandle::new();
+    let (id, session) = started(&discord, PaginatorOptions::new(HOUR).handle(handle.clone())).await;
+
+    handle.close();
+
+    assert_eq!(
+        session.await.unwrap().unwrap(),
+        CancellationType::Programmatic
+    );
+    assert_eq!(discord.dispatcher.count(), 0);
+    assert!(
+        !discord
+            .dispatcher
+            .handle_event(&discord.press_event(&format!("{id}_forward"), OWNER_ID))
+    );
+}
+
+#[tokio::test]
+async fn stops_routing_presses_of_replaced_sessions() {
+    let discord = Discord::start().await;
+    let registry = SessionRegistry::new().per_user_limit(1);
+    let options = || PaginatorOptions::new(HOUR).registry(registry.clone());
+
+    let (_, replaced) = started(&discord, options()).await;
+    let (_, _session) = started(&discord, options()).await;
+
+    assert_eq!(replaced.await.unwrap().unwrap(), CancellationType::Replaced);
+    assert_eq!(discord.dispatcher.count(), 1);
+}
+
+#[tokio::test]
+async fn stops_routing_presses_after_a_shutdown() {
+    let discord = Discord::start().await;
+    let registry = SessionRegistry::new();
+    let options = || PaginatorOptions::new(HOUR).registry(registry.clone());
+
+    let (_, first) = started(&discord, options()).await;
+    let (_, second) = started(&discord, options()).await;
+
+    registry.shutdown().await;
+
+    for session in [first, second] {
+        assert_eq!(session.await.unwrap().unwrap(), CancellationType::Shutdown);
+    }
+    assert_eq!(discord.dispatcher.count(), 0);
+}
+
+#[tokio::test]
+async fn retries_rate_limited_requests() {
+    let discord = Discord::start().await;
+    let (_, session) = started(
+        &discord,
+        PaginatorOptions::new(Duration::from_millis(200))
+            .retry(RetryPolicy::new(1).initial_backoff(Duration::from_millis(10))),
+    )
+    .await;
+
+    discord.fail_next(429, 0);
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Timeout);
+
+    for _ in 0..2 {
+        let request = discord.next_request().await;
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.body["embeds"][0], embed(1, "Timeout"));
+    }
+}
+
+#[tokio::test]
+async fn edits_the_message_once_the_press_cant_be_responded_to() {
+    // The press expired, or was already acknowledged.
+    for (status, code) in [(404, 10062), (400, 40060)] {
+        let discord = Discord::start().await;
+        let (id, session) = started(&discord, TIMEOUT.into()).await;
+
+        discord.fail_next(status, code);
+        discord.press(&format!("{id}_forward")).await;
+
+        let request = discord.next_request().await;
+        assert_eq!(
+            request.path,
+            "/api/v10/interactions/1/press-token-1/callback"
+        );
+
+        assert_eq!(
+            discord.next_request().await,
+            Request {
+                method: "PATCH".to_owned(),
+                path: "/api/v10/webhooks/1/command-token/messages/@original".to_owned(),
+                body: json!({
+                    "embeds": [embed(2, "NotCancelled")],
+                    "components": components(id, "2 / 3", false, false, false),
+                }),
+            },
+            "{code}"
+        );
+        assert!(!session.is_finished());
+    }
+}
@@ -0,0 +1,246 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    poise::{
+        ApplicationContext,
+        serenity_prelude::{
+            ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedAuthor,
+            CreateEmbedFooter,
+        },
+    },
+    poise_paginator::{
+        CancellationType, Event, Interactions, MockRequest, MockTransport, PaginationInfo,
+        PaginatorOptions, Session, View, custom_snapshot,
+    },
+    serde_json::Value,
+    session::{Error, Outcome},
+    std::{sync::Arc, time::Duration},
+    tokio::{sync::mpsc::Sender, task::JoinHandle},
+};
+
+/// Starts a session with two pages on a mock transport, with the embeds of the given generator.
+fn start(
+    options: PaginatorOptions<()>,
+    page: fn(usize) -> CreateEmbed,
+) -> (MockTransport, JoinHandle<Outcome>) {
+    let transport = MockTransport::new();
+    let session = session::spawn(transport.clone(), 2, options, move |index, _| async move {
+        Ok(page(index))
+    });
+
+    (transport, session)
+}
+
+fn options() -> PaginatorOptions<()> {
+    PaginatorOptions::new(Duration::from_secs(60))
+}
+
+fn long(length: usize) -> String {
+    "a".repeat(length)
+}
+
+fn characters(value: &Value) -> usize {
+    value.as_str().unwrap().chars().count()
+}
+
+#[tokio::test(start_paused = true)]
+async fn names_the_text_and_the_page_over_the_limit() {
+    let (_, session) = start(options(), |_| CreateEmbed::new().description(long(5000)));
+
+    assert_eq!(
+        session.await.unwrap().unwrap_err().to_string(),
+        "The description of the page at index 0 has 5000 characters, at most 4096 are allowed"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn fails_on_later_pages_over_the_limit() {
+    let (transport, session) = start(options(), |index| {
+        (0..25 + index).fold(CreateEmbed::new(), |embed, i| {
+            embed.field(i.to_string(), "value", false)
+        })
+    });
+    transport.next_request().await;
+
+    transport.press("1_forward").unwrap();
+
+    assert_eq!(
+        session.await.unwrap().unwrap_err().to_string(),
+        "The embed of the page at index 1 has 26 fields, at most 25 are allowed"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn checks_the_total_length() {
+    let (_, session) = start(options(), |_| {
+        CreateEmbed::new()
+            .title(long(200))
+            .description(long(4000))
+            .field("Name", long(1000), false)
+            .field("Name", long(1000), false)
+    });
+
+    assert_eq!(
+        session.await.unwrap().unwrap_err().to_string(),
+        "The embed of the page at index 0 has 6208 characters in total, at most 6000 are allowed"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn truncates_texts_with_an_ellipsis() {
+    let (transport, _session) = start(options().truncate(true), |_| {
+        CreateEmbed::new()
+            .title(long(300))
+            .description(long(5000))
+            .field(long(300), "value", false)
+            .color(0x3498DB)
+    });
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent");
+    };
+
+    let description = message.embed["description"].as_str().unwrap();
+    assert_eq!(characters(&message.embed["description"]), 4096);
+    assert!(description.ends_with("a…"));
+
+    assert_eq!(characters(&message.embed["title"]), 256);
+    assert_eq!(characters(&message.embed["fields"][0]["name"]), 256);
+    assert_eq!(message.embed["fields"][0]["value"], "value");
+    assert_eq!(message.embed["color"], 0x3498DB);
+}
+
+#[tokio::test(start_paused = true)]
+async fn truncates_to_the_total_length() {
+    let (transport, _session) = start(options().truncate(true), |_| {
+        (0..30).fold(CreateEmbed::new().description(long(3000)), |embed, _| {
+            embed.field("Name", long(1000), false)
+        })
+    });
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent");
+    };
+
+    // Fields are dropped before the description is shortened.
+    let fields = message.embed["fields"].as_array().unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(characters(&message.embed["description"]), 3000);
+
+    let total = characters(&message.embed["description"])
+        + fields
+            .iter()
+            .map(|field| characters(&field["name"]) + characters(&field["value"]))
+            .sum::<usize>();
+    assert!(total <= 6000);
+}
+
+#[tokio::test(start_paused = true)]
+async fn shortens_the_description_if_dropping_fields_isnt_enough() {
+    let (transport, _session) = start(options().truncate(true), |_| {
+        CreateEmbed::new()
+            .title(long(256))
+            .description(long(4096))
+            .author(CreateEmbedAuthor::new(long(256)))
+            .footer(CreateEmbedFooter::new(long(2048)))
+            .field("Name", "value", false)
+    });
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent");
+    };
+
+    assert!(message.embed["fields"].as_array().is_none_or(Vec::is_empty));
+    assert_eq!(
+        characters(&message.embed["description"]),
+        6000 - 256 - 256 - 2048
+    );
+    assert!(
+        message.embed["description"]
+            .as_str()
+            .unwrap()
+            .ends_with('…')
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn leaves_pages_within_the_limits_untouched() {
+    let (transport, _session) = start(options().truncate(true), |_| {
+        CreateEmbed::new().title("Title").description(long(4096))
+    });
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent");
+    };
+
+    assert_eq!(characters(&message.embed["description"]), 4096);
+    assert!(
+        !message.embed["description"]
+            .as_str()
+            .unwrap()
+            .ends_with('…')
+    );
+}
+
+/// A view with six buttons in a row.
+struct CrowdedView;
+
+impl View<(), Error> for CrowdedView {
+    fn create_ids(ctx: ApplicationContext<'_, (), Error>) -> Arc<[String]> {
+        (0..6).map(|i| format!("{}_{i}", ctx.id())).collect()
+    }
+
+    fn rerender_components(
+        ids: Arc<[String]>,
+        _current_idx: usize,
+        _length: usize,
+        disable_all: bool,
+    ) -> Vec<CreateActionRow> {
+        vec![CreateActionRow::Buttons(
+            ids.iter()
+                .map(|id| CreateButton::new(id).label("Button").disabled(disable_all))
+                .collect(),
+        )]
+    }
+
+    async fn on_button_press(
+        _interactions: Arc<dyn Interactions>,
+        _press: ComponentInteraction,
+        _tx: Sender<Event<Error>>,
+        _ids: Arc<[String]>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+struct Crowded;
+
+impl PaginationInfo for Crowded {
+    type PoiseData = ();
+    type PoiseError = Error;
+    type CustomEvent = ();
+    type View = CrowdedView;
+}
+
+#[tokio::test]
+async fn fails_on_components_over_the_limit() {
+    let ids = (0..6).map(|i| format!("1_{i}")).collect();
+
+    let error = custom_snapshot::<Crowded, _, _, _>(
+        (),
+        |_, _, _, _session: Session<()>| async { Ok(CreateEmbed::new().title("Title")) },
+        ids,
+        1,
+        3,
+        CancellationType::NotCancelled,
+        (),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Action row 1 of the page at index 1 has 6 components, at most 5 are allowed"
+    );
+}
@@ -0,0 +1,272 @@
+use poise_paginator::{Navigation, Navigator};
+
+#[test]
+fn starts_on_the_first_page() {
+    let navigator = Navigator::new(5);
+
+    assert_eq!(navigator.index(), 0);
+    assert_eq!(navigator.length(), Some(5));
+    assert!(!navigator.has_previous());
+    assert!(navigator.has_next());
+}
+
+#[test]
+fn has_at_least_one_page() {
+    let mut navigator = Navigator::new(0);
+    assert_eq!(navigator.length(), Some(1));
+
+    navigator.set_length(Some(0));
+    assert_eq!(navigator.length(), Some(1));
+}
+
+#[test]
+fn jumps_only_to_existing_pages() {
+    let mut navigator = Navigator::new(3).with_index(1);
+
+    assert!(!navigator.jump(3));
+    assert_eq!(navigator.index(), 1);
+
+    assert!(navigator.jump(2));
+    assert_eq!(navigator.index(), 2);
+
+    let mut streaming = Navigator::unknown_length();
+    assert!(streaming.jump(100));
+    assert_eq!(streaming.index(), 100);
+}
+
+#[test]
+fn stops_at_the_bounds() {
+    let mut navigator = Navigator::new(3);
+
+    navigator.apply(Navigation::Previous);
+    assert_eq!(navigator.index(), 0);
+
+    navigator.apply(Navigation::Next);
+    navigator.apply(Navigation::Next);
+    navigator.apply(Navigation::Next);
+    assert_eq!(navigator.index(), 2);
+    assert!(!navigator.has_next());
+
+    navigator.apply(Navigation::First);
+    assert_eq!(navigator.index(), 0);
+
+    navigator.apply(Navigation::Last);
+    assert_eq!(navigator.index(), 2);
+}
+
+#[test]
+fn wraps_around() {
+    let mut navigator = Navigator::new(3).wraparound(true);
+    assert!(navigator.has_previous());
+
+    navigator.apply(Navigation::Previous);
+    assert_eq!(navigator.index(), 2);
+
+    navigator.apply(Navigation::Next);
+    assert_eq!(navigator.index(), 0);
+
+    navigator.apply(Navigation::Step(-7));
+    assert_eq!(navigator.index(), 2);
+}
+
+#[test]
+fn a_single_page_never_moves() {
+    let mut navigator = Navigator::new(1).wraparound(true);
+
+    assert!(!navigator.has_previous());
+    assert!(!navigator.has_next());
+
+    for navigation in [
+        Navigation::Previous,
+        Navigation::Next,
+        Navigation::Step(5),
+        Navigation::Last,
+        Navigation::GoTo(3),
+    ] {
+        navigator.apply(navigation);
+        assert_eq!(navigator.index(), 0);
+    }
+}
+
+#[test]
+fn steps_are_clamped() {
+    let mut navigator = Navigator::new(10).with_index(5);
+
+    navigator.apply(Navigation::Step(3));
+    assert_eq!(navigator.index(), 8);
+
+    navigator.apply(Navigation::Step(3));
+    assert_eq!(navigator.index(), 9);
+
+    navigator.apply(Navigation::Step(-20));
+    assert_eq!(navigator.index(), 0);
+}
+
+#[test]
+fn go_to_is_clamped_to_the_last_page() {
+    let mut navigator = Navigator::new(4);
+
+    assert!(navigator.contains(3));
+    assert!(!navigator.contains(4));
+
+    navigator.apply(Navigation::GoTo(2));
+    assert_eq!(navigator.index(), 2);
+
+    navigator.apply(Navigation::GoTo(100));
+    assert_eq!(navigator.index(), 3);
+}
+
+#[test]
+fn shrinking_clamps_the_index() {
+    let mut navigator = Navigator::new(10).with_index(8);
+
+    navigator.set_length(Some(4));
+    assert_eq!(navigator.index(), 3);
+
+    navigator.set_length(Some(20));
+    assert_eq!(navigator.index(), 3);
+}
+
+#[test]
+fn unknown_length_moves_forward_indefinitely() {
+    let mut navigator = Navigator::unknown_length().wraparound(true);
+
+    assert!(navigator.contains(usize::MAX));
+    assert!(navigator.has_next());
+    assert!(!navigator.has_previous());
+
+    navigator.apply(Navigation::Previous);
+    assert_eq!(navigator.index(), 0);
+
+    navigator.apply(Navigation::Step(1_000));
+    assert_eq!(navigator.index(), 1_000);
+
+    navigator.apply(Navigation::Last);
+    assert_eq!(navigator.index(), 1_000);
+
+    navigator.set_length(Some(10));
+    assert_eq!(navigator.index(), 9);
+}
+
+/// A small deterministic pseudo-random generator, so the property tests are reproducible.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn navigation(&mut self) -> Navigation {
+        match self.below(6) {
+            0 => Navigation::First,
+            1 => Navigation::Previous,
+            2 => Navigation::Next,
+            3 => Navigation::Last,
+            4 => Navigation::Step(self.below(41) as isize - 20),
+            _ => Navigation::GoTo(self.below(40) as usize),
+        }
+    }
+
+    fn length(&mut self) -> Option<usize> {
+        match self.below(5) {
+            0 => None,
+            _ => Some(self.below(30) as usize),
+        }
+    }
+}
+
+/// Runs the check for many random navigators and sequences of navigations and length changes.
+fn for_random_sequences(mut check: impl FnMut(Navigator, Navigation, Navigator)) {
+    let mut random = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..1_000 {
+        let mut navigator = match random.length() {
+            Some(length) => Navigator::new(length),
+            None => Navigator::unknown_length(),
+        }
+        .wraparound(random.below(2) == 0);
+
+        for _ in 0..100 {
+            if random.below(10) == 0 {
+                navigator.set_length(random.length());
+            }
+
+            let before = navigator;
+            let navigation = random.navigation();
+            navigator.apply(navigation);
+
+            check(before, navigation, navigator);
+        }
+    }
+}
+
+#[test]
+fn the_index_stays_in_bounds() {
+    for_random_sequences(|_, _, after| {
+        assert!(after.contains(after.index()), "{after:?}");
+    });
+}
+
+#[test]
+fn has_previous_and_has_next_predict_movement() {
+    for_random_sequences(|before, navigation, after| match navigation {
+        Navigation::Previous => {
+            assert_eq!(
+                before.has_previous(),
+                before.index() != after.index(),
+                "{before:?}"
+            )
+        }
+        Navigation::Next => {
+            assert_eq!(
+                before.has_next(),
+                before.index() != after.index(),
+                "{before:?}"
+            )
+        }
+        _ => {}
+    });
+}
+
+#[test]
+fn without_wraparound_moves_are_monotonic() {
+    for_random_sequences(|before, navigation, after| {
+        if before.length().is_none() || before != before.wraparound(false) {
+            return;
+        }
+
+        match navigation {
+            Navigation::Previous | Navigation::First => assert!(after.index() <= before.index()),
+            Navigation::Next | Navigation::Last => assert!(after.index() >= before.index()),
+            Navigation::Step(step) if step < 0 => assert!(after.index() <= before.index()),
+            Navigation::Step(_) => assert!(after.index() >= before.index()),
+            Navigation::GoTo(index) => {
+                assert_eq!(after.index(), index.min(before.length().unwrap() - 1))
+            }
+        }
+    });
+}
+
+#[test]
+fn with_wraparound_stepping_back_undoes_a_step() {
+    for_random_sequences(|before, navigation, after| {
+        let Navigation::Step(step) = navigation else {
+            return;
+        };
+
+        if before == before.wraparound(false) || before.length().is_none() {
+            return;
+        }
+
+        let mut undone = after;
+        undone.apply(Navigation::Step(-step));
+        assert_eq!(undone.index(), before.index(), "{before:?} {step}");
+    });
+}
@@ -0,0 +1,211 @@
+use {
+    poise::serenity_prelude::{ComponentInteraction, CreateEmbed, UserId, futures::StreamExt},
+    poise_paginator::{
+        CancellationType, MockRequest, MockTransport, PersistentId, PersistentPage,
+        PersistentPaginators, Transport,
+    },
+    std::sync::Arc,
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Persistent paginators with three numbered pages under the key `numbers`.
+fn paginators() -> PersistentPaginators<()> {
+    PersistentPaginators::new().register("numbers", |(), index, _: CancellationType| async move {
+        Ok::<_, Error>(PersistentPage::new(
+            CreateEmbed::new().title(format!("Page {}", index + 1)),
+            3,
+        ))
+    })
+}
+
+/// A position of the `numbers` paginator, owned by the user pressing the buttons of the [MockTransport].
+fn position(index: usize, length: usize) -> PersistentId {
+    PersistentId {
+        key: "numbers".to_owned(),
+        owner: UserId::new(1),
+        index,
+        length,
+    }
+}
+
+/// Presses the button with the given id on the transport, returning the interaction.
+async fn press(transport: &MockTransport, custom_id: String) -> ComponentInteraction {
+    let mut presses = transport.presses();
+    transport.press(custom_id).unwrap();
+
+    presses.next().await.expect("The press was received")
+}
+
+#[test]
+fn ids_round_trip() {
+    let id = PersistentId {
+        key: "numbers".to_owned(),
+        owner: UserId::new(20),
+        index: 4,
+        length: 7,
+    };
+
+    assert_eq!(id.to_string(), "pp:numbers:20:4:7");
+    assert_eq!(
+        PersistentId::parse(&format!("{id}_forward")),
+        Some(id.clone())
+    );
+    assert_eq!(
+        PersistentId::parse(&format!("{id}_jump_to.0123456789abcdef")),
+        Some(id)
+    );
+
+    for custom_id in [
+        "1_forward",
+        "pp:numbers:20:4:7",
+        "pp:numbers:owner:4:7_forward",
+        "pq:numbers:20:4:7_forward",
+    ] {
+        assert_eq!(PersistentId::parse(custom_id), None, "{custom_id}");
+    }
+}
+
+#[tokio::test]
+async fn renders_the_next_page() {
+    let transport = MockTransport::new();
+    let press = press(&transport, format!("{}_forward", position(0, 3))).await;
+
+    paginators()
+        .handle_press((), Arc::new(transport.clone()), &press)
+        .await
+        .unwrap();
+
+    let request = transport.next_request().await;
+    let response = &request.response().unwrap()["data"];
+
+    assert_eq!(response["embeds"][0]["title"], "Page 2");
+    assert_eq!(
+        response["components"][0]["components"][3]["custom_id"],
+        "pp:numbers:1:1:3_forward"
+    );
+}
+
+#[tokio::test]
+async fn clamps_the_index_to_the_reported_length() {
+    let transport = MockTransport::new();
+
+    // The message was rendered when there were ten pages, the generator reports three now.
+    let press = press(&transport, format!("{}_forward", position(5, 10))).await;
+
+    paginators()
+        .handle_press((), Arc::new(transport.clone()), &press)
+        .await
+        .unwrap();
+
+    let request = transport.next_request().await;
+    let response = &request.response().unwrap()["data"];
+
+    assert_eq!(response["embeds"][0]["title"], "Page 3");
+    assert_eq!(response["components"][0]["components"][2]["label"], "3 / 3");
+    assert_eq!(
+        response["components"][0]["components"][3]["custom_id"],
+        "pp:numbers:1:2:3_forward"
+    );
+}
+
+#[tokio::test]
+async fn rejects_other_users() {
+    let transport = MockTransport::new();
+
+    let id = PersistentId {
+        owner: UserId::new(2),
+        ..position(0, 3)
+    };
+    let press = press(&transport, format!("{id}_forward")).await;
+
+    paginators()
+        .handle_press((), Arc::new(transport.clone()), &press)
+        .await
+        .unwrap();
+
+    let request = transport.next_request().await;
+    let response = request.response().unwrap();
+
+    // An ephemeral message, the paginator isn't touched.
+    assert_eq!(response["type"], 4);
+    assert_eq!(
+        response["data"]["content"],
+        "You cannot control this paginator."
+    );
+    assert_eq!(response["data"]["flags"], 64);
+    assert!(transport.try_next_request().is_none());
+}
+
+#[tokio::test]
+async fn ignores_unknown_buttons() {
+    for custom_id in [
+        format!("{}_unknown", position(0, 3)),
+        format!(
+            "{}_forward",
+            PersistentId {
+                key: "letters".to_owned(),
+                ..position(0, 3)
+            }
+        ),
+        "1_forward".to_owned(),
+    ] {
+        let transport = MockTransport::new();
+        let press = press(&transport, custom_id).await;
+
+        paginators()
+            .handle_press((), Arc::new(transport.clone()), &press)
+            .await
+            .unwrap();
+
+        assert!(transport.try_next_request().is_none());
+    }
+}
+
+#[tokio::test]
+async fn edits_the_message_after_a_jump() {
+    let transport = MockTransport::new();
+    let press = press(&transport, format!("{}_jump_to", position(0, 3))).await;
+
+    transport.submit_modal(["3"]);
+
+    paginators()
+        .handle_press((), Arc::new(transport.clone()), &press)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Modal { .. }
+    ));
+
+    let MockRequest::Edit(message) = transport.next_request().await else {
+        panic!("The message is edited through the channel");
+    };
+    assert_eq!(message.embed["title"], "Page 3");
+}
+
+#[tokio::test]
+async fn reports_pages_that_dont_exist() {
+    for (input, description) in [
+        ("0", "Page 0 does not exist."),
+        ("4", "Page 4 does not exist."),
+    ] {
+        let transport = MockTransport::new();
+        let press = press(&transport, format!("{}_jump_to", position(0, 3))).await;
+
+        transport.submit_modal([input]);
+
+        paginators()
+            .handle_press((), Arc::new(transport.clone()), &press)
+            .await
+            .unwrap();
+
+        transport.next_request().await;
+
+        let MockRequest::Followup { followup, .. } = transport.next_request().await else {
+            panic!("The error is sent as a followup");
+        };
+        assert_eq!(followup["embeds"][0]["description"], description);
+    }
+}
@@ -0,0 +1,64 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    poise::serenity_prelude::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter},
+    poise_paginator::{MockTransport, PaginatorOptions},
+    std::time::Duration,
+};
+
+#[tokio::test(start_paused = true)]
+async fn renders_the_message_as_text() {
+    let transport = MockTransport::new();
+
+    let _session = session::spawn(
+        transport.clone(),
+        3,
+        PaginatorOptions::new(Duration::from_secs(60)),
+        |index, _| async move {
+            Ok(CreateEmbed::new()
+                .author(CreateEmbedAuthor::new("Author"))
+                .title(format!("Page {}", index + 1))
+                .description("First line\nSecond line")
+                .field("Name", "Value", true)
+                .field("Multiline", "One\nTwo", false)
+                .footer(CreateEmbedFooter::new("Footer")))
+        },
+    );
+
+    transport.next_request().await;
+
+    assert_eq!(
+        transport.message().unwrap().to_string(),
+        "\
+│ Author
+│ Page 1
+│ First line
+│ Second line
+│
+│ Name
+│ Value
+│
+│ Multiline
+│ One
+│ Two
+│
+│ Footer
+(⏪) (◀️) (1 / 3) [▶️] [⏩]
+[Jump to page] [Cancel]
+"
+    );
+}
+
+#[test]
+fn renders_messages_without_an_embed_body() {
+    let message = poise_paginator::MockMessage {
+        embed: serde_json::json!({ "type": "rich" }),
+        components: serde_json::json!([{
+            "type": 1,
+            "components": [{ "type": 2, "style": 2, "custom_id": "1_next", "disabled": false }],
+        }]),
+    };
+
+    assert_eq!(message.to_string(), "[1_next]\n");
+}
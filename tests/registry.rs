@@ -0,0 +1,188 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    poise::serenity_prelude::{
+        ChannelId, ComponentInteraction, CreateActionRow, CreateEmbed, MessageId, UserId,
+        futures::{future::BoxFuture, stream::BoxStream},
+    },
+    poise_paginator::{
+        CancellationToken, CancellationType, Interactions, MockRequest, MockTransport,
+        PaginatorOptions, SessionRegistry, Transport,
+    },
+    session::{Error, Outcome, numbered},
+    std::{sync::Arc, time::Duration},
+    tokio::task::JoinHandle,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A [MockTransport] with its own session id and channel, so that several sessions of the same user can be told apart.
+#[derive(Clone)]
+struct Session {
+    id: u64,
+    channel_id: u64,
+    mock: MockTransport,
+}
+
+impl Transport for Session {
+    fn session_id(&self) -> u64 {
+        self.id
+    }
+
+    fn owner(&self) -> UserId {
+        self.mock.owner()
+    }
+
+    fn channel_id(&self) -> ChannelId {
+        ChannelId::new(self.channel_id)
+    }
+
+    fn send(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<MessageId, Error>> {
+        self.mock.send(embed, components)
+    }
+
+    fn edit(
+        &self,
+        embed: CreateEmbed,
+        components: Vec<CreateActionRow>,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        self.mock.edit(embed, components)
+    }
+
+    fn presses(&self) -> BoxStream<'static, ComponentInteraction> {
+        self.mock.presses()
+    }
+
+    fn interactions(&self) -> Arc<dyn Interactions> {
+        self.mock.interactions()
+    }
+}
+
+/// Starts a session with three pages in the given channel, waiting until its first page was sent.
+async fn start(
+    id: u64,
+    channel_id: u64,
+    options: PaginatorOptions<()>,
+) -> (MockTransport, JoinHandle<Outcome>) {
+    let mock = MockTransport::new();
+
+    let transport = Session {
+        id,
+        channel_id,
+        mock: mock.clone(),
+    };
+    let session = session::spawn(transport, 3, options, numbered);
+
+    // The session registers right after sending the first page.
+    mock.next_request().await;
+    tokio::task::yield_now().await;
+
+    (mock, session)
+}
+
+/// The description of the final page of the session with the given id, which the message is edited to.
+async fn final_description(mock: &MockTransport, id: u64) -> String {
+    let MockRequest::Edit(message) = mock.next_request().await else {
+        panic!("The message is edited directly when the session is closed");
+    };
+
+    assert!(message.is_disabled(&format!("{id}_forward")));
+    message.embed["description"].as_str().unwrap().to_owned()
+}
+
+#[tokio::test(start_paused = true)]
+async fn replaces_the_oldest_session_of_a_user() {
+    let registry = SessionRegistry::new().per_user_limit(1);
+    let options = || PaginatorOptions::new(TIMEOUT).registry(registry.clone());
+
+    let (first, first_session) = start(1, 1, options()).await;
+    let (second, second_session) = start(2, 2, options()).await;
+
+    assert_eq!(
+        first_session.await.unwrap().unwrap(),
+        CancellationType::Replaced
+    );
+    assert_eq!(final_description(&first, 1).await, "Replaced");
+    assert_eq!(registry.count(), 1);
+
+    tokio::task::yield_now().await;
+    assert!(!second_session.is_finished());
+    assert!(second.try_next_request().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn limits_sessions_per_channel() {
+    let registry = SessionRegistry::new().per_channel_limit(2);
+    let options = || PaginatorOptions::new(TIMEOUT).registry(registry.clone());
+
+    let (first, first_session) = start(1, 1, options()).await;
+    let (_other, other_session) = start(2, 2, options()).await;
+    let (_second, second_session) = start(3, 1, options()).await;
+
+    // The limit isn't exceeded yet.
+    assert_eq!(registry.count(), 3);
+
+    let (_third, third_session) = start(4, 1, options()).await;
+
+    assert_eq!(
+        first_session.await.unwrap().unwrap(),
+        CancellationType::Replaced
+    );
+    assert_eq!(final_description(&first, 1).await, "Replaced");
+    assert_eq!(registry.count(), 3);
+
+    for session in [other_session, second_session, third_session] {
+        assert!(!session.is_finished());
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn shuts_down_every_session() {
+    let registry = SessionRegistry::new();
+    let options = || PaginatorOptions::new(TIMEOUT).registry(registry.clone());
+
+    let (first, first_session) = start(1, 1, options()).await;
+    let (second, second_session) = start(2, 1, options()).await;
+
+    registry.shutdown().await;
+
+    // Every session was finalized once the shutdown completed.
+    assert_eq!(registry.count(), 0);
+    assert_eq!(final_description(&first, 1).await, "Shutdown");
+    assert_eq!(final_description(&second, 2).await, "Shutdown");
+
+    for session in [first_session, second_session] {
+        assert_eq!(session.await.unwrap().unwrap(), CancellationType::Shutdown);
+    }
+
+    // Sessions opened afterwards are closed right away.
+    let (late, late_session) = start(3, 1, options()).await;
+
+    assert_eq!(
+        late_session.await.unwrap().unwrap(),
+        CancellationType::Shutdown
+    );
+    assert_eq!(final_description(&late, 3).await, "Shutdown");
+}
+
+#[tokio::test(start_paused = true)]
+async fn ends_with_the_cancellation_token() {
+    let token = CancellationToken::new();
+
+    let (mock, session) = start(
+        1,
+        1,
+        PaginatorOptions::new(TIMEOUT).cancellation_token(token.child_token()),
+    )
+    .await;
+
+    token.cancel();
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Shutdown);
+    assert_eq!(final_description(&mock, 1).await, "Shutdown");
+}
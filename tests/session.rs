@@ -0,0 +1,413 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    poise::serenity_prelude::{CreateEmbed, futures::StreamExt},
+    poise_paginator::{CancellationType, MockRequest, MockTransport, PaginatorOptions, Transport},
+    session::{Outcome, counter, numbered},
+    std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    },
+    tokio::task::JoinHandle,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Starts a session with three pages on a mock transport, taking a second to render the page at `slow_page`.
+fn start(
+    options: PaginatorOptions<()>,
+    slow_page: Option<usize>,
+) -> (MockTransport, JoinHandle<Outcome>) {
+    let transport = MockTransport::new();
+
+    let session = session::spawn(
+        transport.clone(),
+        3,
+        options,
+        move |index, cancellation_type| async move {
+            if Some(index) == slow_page {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+
+            numbered(index, cancellation_type).await
+        },
+    );
+
+    (transport, session)
+}
+
+fn title(transport: &MockTransport) -> String {
+    transport.message().expect("The message was sent").embed["title"]
+        .as_str()
+        .expect("The embed has a title")
+        .to_owned()
+}
+
+#[tokio::test(start_paused = true)]
+async fn sends_the_first_page() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent first");
+    };
+
+    assert_eq!(message.embed["title"], "Page 1");
+    assert!(message.is_disabled("1_fast_rewind"));
+    assert!(message.is_disabled("1_rewind"));
+    assert!(!message.is_disabled("1_forward"));
+    assert!(!message.is_disabled("1_jump_to"));
+    assert_eq!(counter(&transport), "1 / 3");
+}
+
+#[tokio::test(start_paused = true)]
+async fn navigates_through_the_interaction() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_forward").unwrap();
+
+    let request = transport.next_request().await;
+    assert!(matches!(&request, MockRequest::Respond { custom_id, .. } if custom_id == "1_forward"));
+    assert_eq!(request.response().unwrap()["type"], 7);
+    assert_eq!(title(&transport), "Page 2");
+    assert_eq!(counter(&transport), "2 / 3");
+
+    transport.press("1_fast_forward").unwrap();
+    transport.next_request().await;
+
+    let message = transport.message().unwrap();
+    assert_eq!(title(&transport), "Page 3");
+    assert!(message.is_disabled("1_forward"));
+    assert!(!message.is_disabled("1_rewind"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn acknowledges_presses_that_dont_change_the_page() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_rewind").unwrap();
+
+    assert!(transport.next_request().await.is_acknowledgement());
+    assert_eq!(title(&transport), "Page 1");
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelling_disables_all_buttons() {
+    let (transport, session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_cancel").unwrap();
+    transport.next_request().await;
+
+    let message = transport.message().unwrap();
+    assert_eq!(message.embed["description"], "UserInput");
+    assert!(message.is_disabled("1_forward"));
+    assert!(message.is_disabled("1_cancel"));
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::UserInput);
+}
+
+#[tokio::test(start_paused = true)]
+async fn times_out_without_interaction() {
+    let (transport, session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Timeout);
+
+    let MockRequest::Edit(message) = transport.next_request().await else {
+        panic!("The message is edited directly after a timeout");
+    };
+
+    assert_eq!(message.embed["description"], "Timeout");
+    assert!(message.is_disabled("1_jump_to"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn jumps_to_the_submitted_page() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_jump_to").unwrap();
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Modal { custom_id } if custom_id == "1_jump_to"
+    ));
+
+    transport.submit_modal(["3"]);
+
+    // The press was already responded to with the modal.
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Edit(_)
+    ));
+    assert_eq!(title(&transport), "Page 3");
+}
+
+#[tokio::test(start_paused = true)]
+async fn reports_pages_that_dont_exist() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_jump_to").unwrap();
+    transport.next_request().await;
+    transport.submit_modal(["7"]);
+
+    let MockRequest::Followup { followup, .. } = transport.next_request().await else {
+        panic!("The error is sent as a followup");
+    };
+
+    assert_eq!(
+        followup["embeds"][0]["description"],
+        "Page 7 does not exist."
+    );
+    assert_eq!(title(&transport), "Page 1");
+}
+
+#[tokio::test(start_paused = true)]
+async fn reports_page_zero() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_jump_to").unwrap();
+    transport.next_request().await;
+    transport.submit_modal(["0"]);
+
+    let MockRequest::Followup { followup, .. } = transport.next_request().await else {
+        panic!("The error is sent as a followup");
+    };
+
+    assert_eq!(
+        followup["embeds"][0]["description"],
+        "Page 0 does not exist."
+    );
+    assert_eq!(title(&transport), "Page 1");
+}
+
+#[tokio::test(start_paused = true)]
+async fn reports_unknown_buttons() {
+    let (transport, session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_unknown").unwrap();
+
+    let MockRequest::Followup { followup, .. } = transport.next_request().await else {
+        panic!("The error is sent as a followup");
+    };
+
+    assert_eq!(
+        followup["embeds"][0]["description"],
+        "Unknown button 1_unknown"
+    );
+    assert!(!session.is_finished());
+}
+
+#[tokio::test(start_paused = true)]
+async fn dismissed_modals_change_nothing() {
+    let (transport, session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_jump_to").unwrap();
+    transport.next_request().await;
+    transport.dismiss_modal();
+
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::Timeout);
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Edit(_)
+    ));
+    assert_eq!(title(&transport), "Page 1");
+}
+
+#[tokio::test(start_paused = true)]
+async fn shows_the_loading_indicator_for_slow_pages() {
+    let options = PaginatorOptions::new(TIMEOUT)
+        .defer_after(Duration::from_millis(500))
+        .loading_indicator(true);
+
+    let (transport, _session) = start(options, Some(1));
+    transport.next_request().await;
+
+    transport.press("1_forward").unwrap();
+
+    let request = transport.next_request().await;
+    assert_eq!(request.response().unwrap()["type"], 7);
+    assert_eq!(counter(&transport), "⏳ 2 / 3");
+    assert!(transport.message().unwrap().is_disabled("1_forward"));
+    assert_eq!(title(&transport), "Page 1");
+
+    let request = transport.next_request().await;
+    assert!(matches!(request, MockRequest::EditResponse { .. }));
+    assert_eq!(counter(&transport), "2 / 3");
+    assert_eq!(title(&transport), "Page 2");
+}
+
+#[tokio::test(start_paused = true)]
+async fn renders_only_the_latest_of_queued_presses() {
+    let (transport, _session) = start(TIMEOUT.into(), Some(1));
+    transport.next_request().await;
+
+    transport.press("1_forward").unwrap();
+    transport.press("1_forward").unwrap();
+
+    // The slow render of the second page is superseded by the third page.
+    assert!(transport.next_request().await.is_acknowledgement());
+    transport.next_request().await;
+
+    assert_eq!(title(&transport), "Page 3");
+    assert!(transport.try_next_request().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn acknowledges_presses_during_the_cooldown() {
+    let options = PaginatorOptions::new(TIMEOUT).cooldown(Duration::from_secs(1));
+
+    let (transport, _session) = start(options, None);
+    transport.next_request().await;
+
+    transport.press("1_forward").unwrap();
+    transport.press("1_forward").unwrap();
+
+    let requests = [
+        transport.next_request().await,
+        transport.next_request().await,
+    ];
+    assert_eq!(
+        requests.iter().filter(|r| r.is_acknowledgement()).count(),
+        1
+    );
+    assert_eq!(title(&transport), "Page 2");
+}
+
+#[tokio::test(start_paused = true)]
+async fn jumps_to_pages_that_dont_exist_dont_cancel_the_render() {
+    let (transport, _session) = start(TIMEOUT.into(), Some(1));
+    transport.next_request().await;
+
+    // The jump is rejected while the second page is rendered.
+    transport.press("1_forward").unwrap();
+    transport.press("1_jump_to").unwrap();
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Modal { .. }
+    ));
+    transport.submit_modal(["7"]);
+
+    let request = transport.next_request().await;
+    assert!(matches!(&request, MockRequest::Respond { custom_id, .. } if custom_id == "1_forward"));
+    assert_eq!(request.response().unwrap()["type"], 7);
+    assert_eq!(title(&transport), "Page 2");
+    assert_eq!(counter(&transport), "2 / 3");
+
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Followup { .. }
+    ));
+
+    transport.press("1_forward").unwrap();
+    transport.next_request().await;
+    assert_eq!(title(&transport), "Page 3");
+}
+
+#[tokio::test(start_paused = true)]
+async fn keeps_one_jump_modal_open() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    // Opening the second modal closes the first one, so a single submission jumps.
+    transport.press("1_jump_to").unwrap();
+    transport.press("1_jump_to").unwrap();
+
+    for _ in 0..2 {
+        assert!(matches!(
+            transport.next_request().await,
+            MockRequest::Modal { .. }
+        ));
+    }
+
+    transport.submit_modal(["3"]);
+
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Edit(_)
+    ));
+    assert_eq!(title(&transport), "Page 3");
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(transport.try_next_request().is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn opens_a_modal_again_once_dismissed() {
+    let (transport, _session) = start(TIMEOUT.into(), None);
+    transport.next_request().await;
+
+    transport.press("1_jump_to").unwrap();
+    transport.next_request().await;
+    transport.dismiss_modal();
+
+    transport.press("1_jump_to").unwrap();
+    assert!(matches!(
+        transport.next_request().await,
+        MockRequest::Modal { .. }
+    ));
+
+    transport.submit_modal(["2"]);
+    transport.next_request().await;
+    assert_eq!(title(&transport), "Page 2");
+}
+
+#[tokio::test(start_paused = true)]
+async fn refreshes_the_page() {
+    let transport = MockTransport::new();
+    let renders = Arc::new(AtomicUsize::new(0));
+
+    let _session = session::spawn(
+        transport.clone(),
+        3,
+        PaginatorOptions::new(TIMEOUT).refresh_button(true),
+        {
+            let renders = Arc::clone(&renders);
+
+            move |index, _| {
+                let renders = renders.fetch_add(1, Ordering::Relaxed) + 1;
+                async move {
+                    Ok(CreateEmbed::new()
+                        .title(format!("Page {}", index + 1))
+                        .description(format!("Render {renders}")))
+                }
+            }
+        },
+    );
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent first");
+    };
+    assert!(!message.is_disabled("1_refresh"));
+
+    transport.press("1_refresh").unwrap();
+
+    let request = transport.next_request().await;
+    assert!(matches!(&request, MockRequest::Respond { custom_id, .. } if custom_id == "1_refresh"));
+    assert_eq!(title(&transport), "Page 1");
+    assert_eq!(
+        transport.message().unwrap().embed["description"],
+        "Render 2"
+    );
+}
+
+#[tokio::test]
+async fn builds_presses_on_the_resolved_serenity_version() {
+    let transport = MockTransport::new();
+    let mut presses = transport.presses();
+
+    transport.press("1_forward").unwrap();
+
+    let press = presses.next().await.unwrap();
+    assert_eq!(press.data.custom_id, "1_forward");
+    assert_eq!(press.user.id, transport.owner());
+}
@@ -0,0 +1,82 @@
+#[path = "common/session.rs"]
+mod session;
+
+use {
+    poise_paginator::{MockRequest, MockTransport, PaginatorOptions, Signer},
+    session::numbered,
+    std::time::Duration,
+};
+
+#[test]
+fn verifies_signed_ids() {
+    let signer = Signer::new("secret");
+    let signed = signer.sign("1_forward");
+
+    assert!(signed.starts_with("1_forward."));
+    assert!(signer.verify(&signed));
+    assert_eq!(signed, Signer::new("secret").sign("1_forward"));
+}
+
+#[test]
+fn rejects_tampered_ids() {
+    let signer = Signer::new("secret");
+    let signed = signer.sign("1_forward");
+    let (_, signature) = signed.rsplit_once('.').unwrap();
+
+    assert!(!signer.verify(&format!("2_forward.{signature}")));
+    assert!(!signer.verify("1_forward"));
+}
+
+#[test]
+fn rejects_malformed_signatures() {
+    let signer = Signer::new("secret");
+    let signed = signer.sign("1_forward");
+
+    // Truncated, extended, odd-length and non-hex signatures.
+    assert!(!signer.verify(&signed[..signed.len() - 2]));
+    assert!(!signer.verify(&format!("{signed}00")));
+    assert!(!signer.verify(&signed[..signed.len() - 1]));
+    assert!(!signer.verify(&format!("{}zz", &signed[..signed.len() - 2])));
+    assert!(!signer.verify("1_forward."));
+}
+
+#[test]
+fn rejects_ids_signed_with_another_secret() {
+    let signed = Signer::new("secret").sign("1_forward");
+
+    assert!(!Signer::new("another secret").verify(&signed));
+}
+
+#[tokio::test(start_paused = true)]
+async fn drops_forged_presses() {
+    let signer = Signer::new("secret");
+    let transport = MockTransport::new();
+
+    let _session = session::spawn(
+        transport.clone(),
+        3,
+        PaginatorOptions::new(Duration::from_secs(60)).signer(signer.clone()),
+        numbered,
+    );
+
+    let MockRequest::Send(message) = transport.next_request().await else {
+        panic!("The first page is sent first");
+    };
+    let forward = signer.sign("1_forward");
+    assert!(!message.is_disabled(&forward));
+
+    // Neither responded to nor passed to the view.
+    transport.press("1_forward").unwrap();
+    transport.press(format!("{forward}.00")).unwrap();
+    transport
+        .press(Signer::new("another secret").sign("1_forward"))
+        .unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(transport.try_next_request().is_none());
+
+    transport.press(&forward).unwrap();
+
+    let request = transport.next_request().await;
+    assert!(matches!(&request, MockRequest::Respond { custom_id, .. } if *custom_id == forward));
+    assert_eq!(transport.message().unwrap().embed["title"], "Page 2");
+}
@@ -0,0 +1,104 @@
+use {
+    poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter},
+    poise_paginator::{
+        CancellationType, DefaultViewWithRefresh, PaginationInfo, Session, custom_snapshot,
+        snapshot,
+    },
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+async fn generator(
+    _cx: (),
+    index: usize,
+    cancellation_type: CancellationType,
+    session: Session<Vec<&'static str>>,
+) -> Result<CreateEmbed, Error> {
+    let pages = session.state().await;
+
+    let embed = CreateEmbed::new().title("Fruits").description(pages[index]);
+
+    Ok(match cancellation_type {
+        CancellationType::NotCancelled => embed,
+        cancellation_type => embed.footer(CreateEmbedFooter::new(format!("{cancellation_type:?}"))),
+    })
+}
+
+fn pages() -> Vec<&'static str> {
+    vec!["Apple", "Banana", "Cherry"]
+}
+
+#[tokio::test]
+async fn middle_page() {
+    snapshot((), generator, 1, 3, CancellationType::NotCancelled, pages())
+        .await
+        .unwrap()
+        .assert_matches("tests/snapshots/middle_page.json");
+}
+
+#[tokio::test]
+async fn timed_out_last_page() {
+    snapshot((), generator, 2, 3, CancellationType::Timeout, pages())
+        .await
+        .unwrap()
+        .assert_matches("tests/snapshots/timed_out_last_page.json");
+}
+
+struct WithRefresh;
+
+impl PaginationInfo for WithRefresh {
+    type PoiseData = ();
+    type PoiseError = Error;
+    type CustomEvent = ();
+    type View = DefaultViewWithRefresh;
+}
+
+#[tokio::test]
+async fn custom_view() {
+    let ids = [
+        "fast_rewind",
+        "rewind",
+        "counter",
+        "forward",
+        "fast_forward",
+        "jump_to",
+        "cancel",
+        "refresh",
+    ]
+    .map(|name| format!("7_{name}"))
+    .into();
+
+    custom_snapshot::<WithRefresh, _, _, _>(
+        (),
+        generator,
+        ids,
+        0,
+        3,
+        CancellationType::NotCancelled,
+        pages(),
+    )
+    .await
+    .unwrap()
+    .assert_matches("tests/snapshots/custom_view.json");
+}
+
+#[tokio::test]
+async fn clamps_the_index_to_the_length_set_by_the_generator() {
+    let snapshot = snapshot(
+        (),
+        |_, index, _, session: Session<()>| async move {
+            session.set_length(2);
+            Ok(CreateEmbed::new().title(format!("Page {}", index + 1)))
+        },
+        4,
+        5,
+        CancellationType::NotCancelled,
+        (),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!((snapshot.index, snapshot.length), (1, 2));
+    assert_eq!(snapshot.embed["title"], "Page 2");
+    assert_eq!(snapshot.components[0]["components"][2]["label"], "2 / 2");
+}
@@ -0,0 +1,187 @@
+use {
+    discord_stand_in::{CHANNEL_ID, Discord, MESSAGE_ID, OWNER_ID},
+    poise::serenity_prelude::{ChannelId, CreateEmbed, MessageId, UserId},
+    poise_paginator::{
+        CancellationType, FileSessionStore, MemorySessionStore, PaginatorOptions, Session,
+        SessionRecord, SessionStore, resume,
+    },
+    serde_json::json,
+    std::{path::PathBuf, sync::Arc, time::Duration},
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A record of an open session on the message of the stand-in server, on the second of three pages.
+fn record(session_id: u64) -> SessionRecord {
+    SessionRecord {
+        session_id,
+        channel_id: ChannelId::new(CHANNEL_ID),
+        message_id: MessageId::new(MESSAGE_ID),
+        owner: UserId::new(OWNER_ID),
+        index: 1,
+        length: 3,
+        state: json!({ "visits": 4 }),
+    }
+}
+
+/// A directory for the files of a test, which is empty when the test starts.
+fn directory(test: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("poise_paginator-{}-{test}", std::process::id()));
+
+    std::fs::remove_dir_all(&directory).ok();
+    std::fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+#[tokio::test]
+async fn file_stores_keep_records_across_reopening() {
+    let path = directory("reopen").join("sessions.json");
+
+    let store = FileSessionStore::open(&path).await.unwrap();
+    assert!(store.list().await.unwrap().is_empty());
+
+    store.save(record(1)).await.unwrap();
+    store.save(record(2)).await.unwrap();
+    store
+        .save(SessionRecord {
+            index: 2,
+            ..record(1)
+        })
+        .await
+        .unwrap();
+
+    let reopened = FileSessionStore::open(&path).await.unwrap();
+    assert_eq!(reopened.list().await.unwrap().len(), 2);
+    assert_eq!(reopened.load(1).await.unwrap().unwrap().index, 2);
+    assert_eq!(reopened.load(2).await.unwrap(), Some(record(2)));
+    assert_eq!(reopened.load(3).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn file_stores_remove_records() {
+    let path = directory("remove").join("sessions.json");
+
+    let store = FileSessionStore::open(&path).await.unwrap();
+    store.save(record(1)).await.unwrap();
+    store.save(record(2)).await.unwrap();
+    store.remove(1).await.unwrap();
+
+    // Removing a record that doesn't exist is fine.
+    store.remove(3).await.unwrap();
+
+    let reopened = FileSessionStore::open(&path).await.unwrap();
+    assert_eq!(reopened.list().await.unwrap(), vec![record(2)]);
+}
+
+#[tokio::test]
+async fn file_stores_replace_the_file_through_a_temporary_file() {
+    let directory = directory("temporary");
+    let path = directory.join("sessions.json");
+
+    let store = FileSessionStore::open(&path).await.unwrap();
+    store.save(record(1)).await.unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files, ["sessions.json"]);
+
+    let records: Vec<SessionRecord> =
+        serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(records, [record(1)]);
+}
+
+#[tokio::test]
+async fn file_stores_reject_corrupt_files() {
+    let path = directory("corrupt").join("sessions.json");
+    std::fs::write(&path, "[{").unwrap();
+
+    assert!(FileSessionStore::open(&path).await.is_err());
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Visits {
+    visits: usize,
+}
+
+#[tokio::test]
+async fn resumes_sessions_from_their_record() {
+    let discord = Discord::start().await;
+    let store = Arc::new(MemorySessionStore::new());
+
+    let session = tokio::spawn({
+        let http = discord.http.clone();
+        let dispatcher = discord.dispatcher.clone();
+        let store = Arc::clone(&store) as Arc<dyn SessionStore>;
+
+        async move {
+            resume(
+                (),
+                http,
+                dispatcher,
+                record(42),
+                |_, index, _, session: Session<Visits>| async move {
+                    let visits = {
+                        let mut state = session.state().await;
+                        state.visits += 1;
+                        state.visits
+                    };
+
+                    Ok::<_, Error>(
+                        CreateEmbed::new()
+                            .title(format!("Page {}", index + 1))
+                            .description(format!("Visit {visits}")),
+                    )
+                },
+                PaginatorOptions::new(Duration::from_secs(10)).store(store),
+            )
+            .await
+        }
+    });
+
+    // The recorded page is edited into the existing message, with the recorded state.
+    let request = discord.next_request().await;
+    assert_eq!(request.method, "PATCH");
+    assert_eq!(
+        request.path,
+        format!("/api/v10/channels/{CHANNEL_ID}/messages/{MESSAGE_ID}")
+    );
+    assert_eq!(request.body["embeds"][0]["title"], "Page 2");
+    assert_eq!(request.body["embeds"][0]["description"], "Visit 5");
+    assert_eq!(
+        request.body["components"][0]["components"][3]["custom_id"],
+        "42_forward"
+    );
+
+    // The buttons of the message still work.
+    discord.press("42_forward").await;
+
+    let request = discord.next_request().await;
+    assert_eq!(request.body["type"], 7);
+    assert_eq!(request.body["data"]["embeds"][0]["title"], "Page 3");
+    assert_eq!(request.body["data"]["embeds"][0]["description"], "Visit 6");
+
+    // The record is saved once the message was updated.
+    let mut saved = None;
+
+    for _ in 0..100 {
+        saved = store.load(42).await.unwrap();
+
+        if saved.as_ref().is_some_and(|saved| saved.index == 2) {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let saved = saved.unwrap();
+    assert_eq!(saved.index, 2);
+    assert_eq!(saved.state, json!({ "visits": 6 }));
+
+    discord.press("42_cancel").await;
+    assert_eq!(session.await.unwrap().unwrap(), CancellationType::UserInput);
+    assert_eq!(store.load(42).await.unwrap(), None);
+}